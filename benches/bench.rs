@@ -1,6 +1,6 @@
-#![no_coverage]
-use criterion::{black_box, criterion_group, criterion_main, Bencher, Criterion};
-use ringbuffer::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Bencher, Criterion};
+use ringbuffer::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer, SliceRingBuffer};
+use std::mem::MaybeUninit;
 
 fn benchmark_push<T: RingBuffer<i32>, F: Fn() -> T>(b: &mut Bencher, new: F) {
     b.iter(|| {
@@ -15,6 +15,33 @@ fn benchmark_push<T: RingBuffer<i32>, F: Fn() -> T>(b: &mut Bencher, new: F) {
     })
 }
 
+fn benchmark_push_boxed<const N: usize>(b: &mut Bencher) {
+    b.iter(|| {
+        let mut rb = ConstGenericRingBuffer::<i32, N>::new_boxed();
+
+        for i in 0..1_000_000 {
+            rb.push(i);
+            black_box(());
+        }
+
+        rb
+    })
+}
+
+fn benchmark_push_slice<const N: usize>(b: &mut Bencher) {
+    b.iter(|| {
+        let mut storage = [MaybeUninit::<i32>::uninit(); N];
+        let mut rb = SliceRingBuffer::new(&mut storage);
+
+        for i in 0..1_000_000 {
+            rb.push(i);
+            black_box(());
+        }
+
+        black_box(&rb);
+    })
+}
+
 fn benchmark_push_dequeue<T: RingBuffer<i32>, F: Fn() -> T>(b: &mut Bencher, new: F) {
     b.iter(|| {
         let mut rb = new();
@@ -63,6 +90,49 @@ fn benchmark_various<T: RingBuffer<i32>, F: Fn() -> T>(b: &mut Bencher, new: F)
     })
 }
 
+fn benchmark_eq<T: RingBuffer<u8> + PartialEq, F: Fn() -> T>(b: &mut Bencher, new: F) {
+    let mut rb1 = new();
+    let mut rb2 = new();
+
+    // give both buffers a different wrap position
+    rb1.extend((0..rb1.capacity() + 100).map(|i| i as u8));
+    rb2.extend((0..rb2.capacity() + 1000).map(|i| i.wrapping_sub(900) as u8));
+
+    b.iter(|| {
+        assert!(black_box(&rb1) == black_box(&rb2));
+    })
+}
+
+fn benchmark_drop<T: RingBuffer<u8>, F: Fn() -> T>(b: &mut Bencher, new: F) {
+    b.iter_batched(
+        || {
+            let mut rb = new();
+            rb.fill(0);
+            rb
+        },
+        drop,
+        BatchSize::LargeInput,
+    )
+}
+
+fn benchmark_fill_reset(b: &mut Bencher) {
+    let mut rb = AllocRingBuffer::<u32>::new(65536);
+    b.iter(|| {
+        rb.fill(1);
+        rb.reset();
+        black_box(&rb);
+    })
+}
+
+fn benchmark_fill_clear(b: &mut Bencher) {
+    let mut rb = AllocRingBuffer::<u32>::new(65536);
+    b.iter(|| {
+        rb.fill(1);
+        rb.clear();
+        black_box(&rb);
+    })
+}
+
 macro_rules! generate_benches {
     (called, $c: tt, $rb: tt, $ty: tt, $fn: tt, $bmfunc: tt, $($i:tt),*) => {
         $(
@@ -182,5 +252,63 @@ fn criterion_benchmark(c: &mut Criterion) {
     ];
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn criterion_benchmark_boxed(c: &mut Criterion) {
+    // compile time capacity on the heap, compared to the runtime capacity of AllocRingBuffer
+    c.bench_function(
+        "Boxed ConstGenericRingBuffer benchmark_push 1M capacity 1024",
+        benchmark_push_boxed::<1024>,
+    );
+    c.bench_function(
+        "Boxed ConstGenericRingBuffer benchmark_push 1M capacity 8192",
+        benchmark_push_boxed::<8192>,
+    );
+}
+
+fn criterion_benchmark_slice(c: &mut Criterion) {
+    // the capacity of a slice is only known at runtime, powers of two use a bitmask to wrap
+    c.bench_function(
+        "SliceRingBuffer benchmark_push 1M capacity 16",
+        benchmark_push_slice::<16>,
+    );
+    c.bench_function(
+        "SliceRingBuffer benchmark_push 1M capacity not power of two 17",
+        benchmark_push_slice::<17>,
+    );
+}
+
+fn criterion_benchmark_eq(c: &mut Criterion) {
+    c.bench_function("AllocRingBuffer eq 64K u8", |b| {
+        benchmark_eq(b, || AllocRingBuffer::<u8>::new(65536))
+    });
+    c.bench_function("ConstGenericRingBuffer eq 64K u8", |b| {
+        benchmark_eq(b, ConstGenericRingBuffer::<u8, 65536>::new)
+    });
+}
+
+fn criterion_benchmark_reset(c: &mut Criterion) {
+    c.bench_function(
+        "AllocRingBuffer fill and reset 64K u32",
+        benchmark_fill_reset,
+    );
+    c.bench_function(
+        "AllocRingBuffer fill and clear 64K u32",
+        benchmark_fill_clear,
+    );
+}
+
+fn criterion_benchmark_drop(c: &mut Criterion) {
+    c.bench_function("AllocRingBuffer drop full 1M u8", |b| {
+        benchmark_drop(b, || AllocRingBuffer::<u8>::new(1 << 20))
+    });
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    criterion_benchmark_boxed,
+    criterion_benchmark_slice,
+    criterion_benchmark_eq,
+    criterion_benchmark_drop,
+    criterion_benchmark_reset
+);
 criterion_main!(benches);
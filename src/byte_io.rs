@@ -0,0 +1,103 @@
+//! Helpers for moving bytes between the regions of a ringbuffer and `std::io` sources and
+//! sinks.
+
+use core::mem::MaybeUninit;
+use core::slice;
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+/// Reads at most `max` bytes from `reader` into `regions`, which are filled in order. The second
+/// region is only read into when the first one was filled completely, so this calls `read` at
+/// most twice. Returns the number of bytes read, which are initialized at the start of the
+/// regions.
+///
+/// When reading into the second region fails, the bytes already read into the first one are
+/// still reported. The error will usually be returned again by the next read. A reader which
+/// reports more bytes than the region holds fails with [`ErrorKind::InvalidData`].
+pub(crate) fn read_into_regions<R: Read>(
+    reader: &mut R,
+    regions: (&mut [MaybeUninit<u8>], &mut [MaybeUninit<u8>]),
+    max: usize,
+) -> Result<usize> {
+    let mut total = 0;
+    for region in [regions.0, regions.1] {
+        let len = region.len().min(max - total);
+        if len == 0 {
+            break;
+        }
+
+        // `read` takes an initialized slice, so the region is zeroed first
+        let region = &mut region[..len];
+        for byte in region.iter_mut() {
+            *byte = MaybeUninit::new(0);
+        }
+        // Safety: every byte of the region was just initialized
+        let region = unsafe { slice::from_raw_parts_mut(region.as_mut_ptr().cast::<u8>(), len) };
+
+        let n = loop {
+            match reader.read(region).and_then(|n| checked_count(n, len)) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) if total > 0 => return Ok(total),
+                Err(e) => return Err(e),
+            }
+        };
+        total += n;
+        if n < len {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Writes at most `max` bytes from `regions` to `writer`, in order. The second region is only
+/// written when the first one was written completely, so this calls `write` at most twice.
+/// Returns the number of bytes written, counting from the start of the first region.
+///
+/// Like [`read_into_regions`], an error while writing the second region is not reported when
+/// part of the first one was written already, and a writer which reports more bytes than it was
+/// given fails with [`ErrorKind::InvalidData`].
+pub(crate) fn write_from_regions<W: Write>(
+    writer: &mut W,
+    regions: (&[u8], &[u8]),
+    max: usize,
+) -> Result<usize> {
+    let mut total = 0;
+    for region in [regions.0, regions.1] {
+        let len = region.len().min(max - total);
+        if len == 0 {
+            break;
+        }
+
+        let n = loop {
+            match writer
+                .write(&region[..len])
+                .and_then(|n| checked_count(n, len))
+            {
+                Ok(n) => break n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) if total > 0 => return Ok(total),
+                Err(e) => return Err(e),
+            }
+        };
+        total += n;
+        if n < len {
+            break;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Rejects a count of bytes larger than the `len` bytes passed to `read` or `write`. Both traits
+/// are safe to implement, so the count can't be trusted to advance the pointers with.
+fn checked_count(n: usize, len: usize) -> Result<usize> {
+    if n <= len {
+        Ok(n)
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "reported more bytes than the buffer holds",
+        ))
+    }
+}
@@ -0,0 +1,127 @@
+use crate::RingBuffer;
+use core::marker::PhantomData;
+use core::mem;
+
+/// A view into the newest element of a ringbuffer, returned by
+/// [`last_entry`](RingBuffer::last_entry).
+///
+/// It mutably borrows the buffer, and allows updating the newest element in place, replacing it,
+/// or pushing a new element after inspecting it. This supports update-or-append patterns, like
+/// merging a sample into the current bucket of a time series, or starting a new bucket.
+///
+/// # Example
+/// ```
+/// use ringbuffer::{AllocRingBuffer, RingBuffer};
+///
+/// // buckets of (minute, count)
+/// let mut rb = AllocRingBuffer::new(4);
+/// rb.push((0, 1));
+///
+/// for minute in [0, 0, 1] {
+///     let mut entry = rb.last_entry().unwrap();
+///     if entry.get().0 == minute {
+///         entry.get_mut().1 += 1;
+///     } else {
+///         entry.push((minute, 1));
+///     }
+/// }
+/// assert_eq!(rb.to_vec(), vec![(0, 3), (1, 1)]);
+/// ```
+#[derive(Debug)]
+pub struct LastEntry<'a, T, RB: RingBuffer<T>> {
+    // never empty while the entry exists, since only the entry can modify it
+    rb: &'a mut RB,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T, RB: RingBuffer<T>> LastEntry<'a, T, RB> {
+    #[inline]
+    pub(crate) fn new(rb: &'a mut RB) -> Option<Self> {
+        if rb.is_empty() {
+            None
+        } else {
+            Some(Self {
+                rb,
+                phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns a reference to the newest element.
+    #[must_use]
+    pub fn get(&self) -> &T {
+        self.rb.back().expect("the buffer is not empty")
+    }
+
+    /// Returns a mutable reference to the newest element.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.rb.back_mut().expect("the buffer is not empty")
+    }
+
+    /// Converts the entry into a mutable reference to the newest element, which lives as long
+    /// as the borrow of the buffer.
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut T {
+        self.rb.back_mut().expect("the buffer is not empty")
+    }
+
+    /// Replaces the newest element with `value`, and returns the element it replaced.
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Pushes `value` onto the buffer after the current newest element, which may overwrite
+    /// the oldest element like [`push`](RingBuffer::push). Returns a mutable reference to the
+    /// new element.
+    pub fn push(self, value: T) -> &'a mut T {
+        self.rb.push(value);
+        self.rb.back_mut().expect("the buffer is not empty")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer};
+
+    #[test]
+    fn test_last_entry() {
+        let mut rb = AllocRingBuffer::new(2);
+        assert!(rb.last_entry().is_none());
+
+        rb.push(1);
+        let mut entry = rb.last_entry().unwrap();
+        assert_eq!(entry.get(), &1);
+        *entry.get_mut() += 10;
+        assert_eq!(rb.to_vec(), alloc::vec![11]);
+
+        let mut entry = rb.last_entry().unwrap();
+        assert_eq!(entry.replace(2), 11);
+        assert_eq!(rb.to_vec(), alloc::vec![2]);
+
+        *rb.last_entry().unwrap().push(3) += 1;
+        assert_eq!(rb.to_vec(), alloc::vec![2, 4]);
+
+        // pushing onto a full buffer overwrites the oldest element
+        let entry = rb.last_entry().unwrap();
+        assert_eq!(entry.push(5), &mut 5);
+        assert_eq!(rb.to_vec(), alloc::vec![4, 5]);
+
+        *rb.last_entry().unwrap().into_mut() = 6;
+        assert_eq!(rb.to_vec(), alloc::vec![4, 6]);
+    }
+
+    #[test]
+    fn test_last_entry_const_generic() {
+        let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+        for value in [1, 1, 2, 2, 2, 3] {
+            match rb.last_entry() {
+                Some(mut entry) if *entry.get() / 10 == value => *entry.get_mut() += 1,
+                Some(entry) => {
+                    let _ = entry.push(value * 10);
+                }
+                None => rb.push(value * 10),
+            }
+        }
+        assert_eq!(rb.to_vec(), alloc::vec![11, 22, 30]);
+    }
+}
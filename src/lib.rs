@@ -15,11 +15,36 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[macro_use]
 pub(crate) mod ringbuffer_trait;
 
-pub use ringbuffer_trait::RingBuffer;
+pub use ringbuffer_trait::{ExtendReport, IndexError, InvariantError, RingBuffer};
+
+mod entry;
+pub use entry::LastEntry;
+
+mod policy;
+pub use policy::{OverwriteOldest, OverwritePolicy};
+
+mod reader;
+pub use reader::{Lagged, Reader};
+
+mod rolling_hash;
+pub use rolling_hash::RollingHash;
+
+#[cfg(feature = "std")]
+mod byte_io;
+
+#[cfg(feature = "std")]
+pub mod mpsc;
+
+#[cfg(feature = "alloc")]
+mod rolling_extreme;
+#[cfg(feature = "alloc")]
+pub use rolling_extreme::RollingExtreme;
 
 #[cfg(feature = "alloc")]
 mod with_alloc;
@@ -29,11 +54,56 @@ pub use with_alloc::alloc_ringbuffer::AllocRingBuffer;
 pub use with_alloc::vecdeque::GrowableAllocRingBuffer;
 
 mod with_const_generics;
-pub use with_const_generics::ConstGenericRingBuffer;
+pub use with_const_generics::{CapacityError, ConstGenericRingBuffer};
+
+mod with_slice;
+pub use with_slice::SliceRingBuffer;
+
+/// Marker for types for which a value with all bits set to zero is valid, like the numeric
+/// primitives. Used by [`AllocRingBuffer::with_capacity_zeroed`].
+///
+/// # Safety
+/// Only implement this for types where the all-zero bit pattern is a valid value.
+pub unsafe trait Zeroable {}
+
+macro_rules! impl_zeroable {
+    ($($t: ty),*) => {
+        $(unsafe impl Zeroable for $t {})*
+    };
+}
+
+impl_zeroable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+
+/// Returns the number of elements an [`AllocRingBuffer`] allocates for a requested capacity,
+/// which is the smallest power of two greater than or equal to `requested`. A request of 0
+/// gives 1. Because this is a `const fn`, it can be used to size related arrays at compile time.
+///
+/// This is the [`buffer_size`](RingBuffer::buffer_size) of the buffer, the capacity stays as
+/// requested.
+///
+/// # Panics
+/// Panics in debug builds when the result doesn't fit in a `usize`, like
+/// [`usize::next_power_of_two`]
+///
+/// ```
+/// use ringbuffer::{next_capacity_power_of_two, AllocRingBuffer, RingBuffer};
+///
+/// const SIZE: usize = next_capacity_power_of_two(100);
+/// let scratch = [0u8; SIZE];
+///
+/// let rb = AllocRingBuffer::<u8>::new(100);
+/// assert_eq!(rb.buffer_size(), scratch.len());
+/// ```
+#[inline]
+#[must_use]
+pub const fn next_capacity_power_of_two(requested: usize) -> usize {
+    requested.next_power_of_two()
+}
 
 /// Used internally. Computes the bitmask used to properly wrap the ringbuffers.
 #[inline]
-#[cfg(feature = "alloc")]
 const fn mask_and(cap: usize, index: usize) -> usize {
     debug_assert!(cap.is_power_of_two());
     index & (cap - 1)
@@ -51,11 +121,15 @@ mod tests {
     extern crate std;
 
     use core::fmt::Debug;
+    use core::ops::AddAssign;
     use std::vec;
     use std::vec::Vec;
 
     use crate::ringbuffer_trait::{RingBufferIterator, RingBufferMutIterator};
-    use crate::{AllocRingBuffer, ConstGenericRingBuffer, GrowableAllocRingBuffer, RingBuffer};
+    use crate::{
+        next_capacity_power_of_two, AllocRingBuffer, ConstGenericRingBuffer, ExtendReport,
+        GrowableAllocRingBuffer, IndexError, RingBuffer,
+    };
 
     #[test]
     fn run_test_neg_index() {
@@ -117,7 +191,7 @@ mod tests {
             b.push(1);
             assert_eq!(1, b.len());
             b.push(2);
-            assert_eq!(2, b.len())
+            assert_eq!(2, b.len());
         }
 
         test_len(AllocRingBuffer::new(8));
@@ -417,7 +491,7 @@ mod tests {
                 *el += 1;
             }
 
-            assert_eq!(vec![2, 3, 4], b.to_vec())
+            assert_eq!(vec![2, 3, 4], b.to_vec());
         }
 
         test_iter_mut(AllocRingBuffer::new(8));
@@ -441,7 +515,7 @@ mod tests {
                 *el += 1;
             }
 
-            assert_eq!(vec![2, 3, 4], b.to_vec())
+            assert_eq!(vec![2, 3, 4], b.to_vec());
         }
 
         test_iter_mut(AllocRingBuffer::new(8));
@@ -460,7 +534,7 @@ mod tests {
                 *i += 1;
             }
 
-            assert_eq!(vec![3, 4], b.to_vec())
+            assert_eq!(vec![3, 4], b.to_vec());
         }
 
         run_test_iter_mut_wrap(AllocRingBuffer::new(2));
@@ -477,7 +551,7 @@ mod tests {
             *i += 1;
         }
 
-        assert_eq!(vec![2, 3, 4], b.to_vec())
+        assert_eq!(vec![2, 3, 4], b.to_vec());
     }
 
     #[test]
@@ -493,7 +567,7 @@ mod tests {
                 *i += 1;
             }
 
-            assert_eq!(vec![3, 4], b.to_vec())
+            assert_eq!(vec![3, 4], b.to_vec());
         }
 
         run_test_iter_mut_wrap(AllocRingBuffer::new(2));
@@ -511,7 +585,7 @@ mod tests {
             *i += 1;
         }
 
-        assert_eq!(vec![2, 3, 4], b.to_vec())
+        assert_eq!(vec![2, 3, 4], b.to_vec());
     }
 
     #[test]
@@ -521,7 +595,7 @@ mod tests {
             b.push(2);
             b.push(3);
 
-            assert_eq!(vec![1, 2, 3], b.to_vec())
+            assert_eq!(vec![1, 2, 3], b.to_vec());
         }
 
         test_to_vec(AllocRingBuffer::new(8));
@@ -537,7 +611,7 @@ mod tests {
             // Wrap
             b.push(3);
 
-            assert_eq!(vec![2, 3], b.to_vec())
+            assert_eq!(vec![2, 3], b.to_vec());
         }
 
         test_to_vec_wrap(AllocRingBuffer::new(2));
@@ -550,14 +624,14 @@ mod tests {
         b.push(2);
         b.push(3);
 
-        assert_eq!(vec![1, 2, 3], b.to_vec())
+        assert_eq!(vec![1, 2, 3], b.to_vec());
     }
 
     #[test]
     fn run_test_index() {
         fn test_index(mut b: impl RingBuffer<i32>) {
             b.push(2);
-            assert_eq!(b[0], 2)
+            assert_eq!(b[0], 2);
         }
 
         test_index(AllocRingBuffer::new(8));
@@ -776,7 +850,7 @@ mod tests {
         fn test_from_iterator<T: RingBuffer<i32> + FromIterator<i32>>() {
             let b: T = std::iter::repeat(1).take(1024).collect();
             assert_eq!(b.len(), 1024);
-            assert_eq!(b.to_vec(), vec![1; 1024])
+            assert_eq!(b.to_vec(), vec![1; 1024]);
         }
 
         test_from_iterator::<GrowableAllocRingBuffer<i32>>();
@@ -788,7 +862,7 @@ mod tests {
         fn test_from_iterator_wrap<T: RingBuffer<i32> + FromIterator<i32>>() {
             let b: T = std::iter::repeat(1).take(8000).collect();
             assert_eq!(b.len(), b.capacity());
-            assert_eq!(b.to_vec(), vec![1; b.capacity()])
+            assert_eq!(b.to_vec(), vec![1; b.capacity()]);
         }
 
         test_from_iterator_wrap::<GrowableAllocRingBuffer<i32>>();
@@ -981,7 +1055,7 @@ mod tests {
             b.skip();
             b.skip();
 
-            assert_eq!(b.len(), 0)
+            assert_eq!(b.len(), 0);
         }
 
         test_skip(AllocRingBuffer::new(8));
@@ -1189,7 +1263,7 @@ mod tests {
 
         assert_eq!(b.get_signed(-1), Some(&2));
         assert_eq!(b.get_signed(-2), Some(&1));
-        assert_eq!(b.get_signed(-3), Some(&0))
+        assert_eq!(b.get_signed(-3), Some(&0));
     }
 
     #[test]
@@ -1446,4 +1520,1753 @@ mod tests {
         test_clone!(GrowableAllocRingBuffer::<_>::new());
         test_clone!(AllocRingBuffer::<_>::new(4));
     }
+
+    #[test]
+    fn run_test_as_slices() {
+        fn test_as_slices(mut rb: impl RingBuffer<i32>) {
+            let (a, b) = rb.as_slices();
+            assert!(a.is_empty() && b.is_empty());
+
+            for i in 0..6 {
+                rb.push(i);
+            }
+            let (a, b) = rb.as_slices();
+            assert_eq!([a, b].concat(), rb.to_vec());
+
+            let (a, b) = rb.as_mut_slices();
+            a.iter_mut().chain(b.iter_mut()).for_each(|i| *i *= 2);
+            assert_eq!(rb.to_vec(), vec![4, 6, 8, 10]);
+        }
+
+        test_as_slices(AllocRingBuffer::new(4));
+        test_as_slices(ConstGenericRingBuffer::<i32, 4>::new());
+
+        // the growable buffer never wraps around, so emulate it
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        rb.extend([0, 1, 2, 3, 4, 5]);
+        rb.skip();
+        rb.skip();
+        assert_eq!(rb.len(), 4);
+        let (a, b) = rb.as_mut_slices();
+        a.iter_mut().chain(b.iter_mut()).for_each(|i| *i *= 2);
+        let (a, b) = rb.as_slices();
+        assert_eq!([a, b].concat(), vec![4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn run_test_dequeue_batch() {
+        fn test_dequeue_batch(mut rb: impl RingBuffer<i32>) {
+            for i in 0..6 {
+                rb.push(i);
+            }
+
+            let mut seen = vec![];
+            let mut calls = 0;
+            let consumed = rb.dequeue_batch(3, |s| {
+                seen.extend_from_slice(s);
+                calls += 1;
+            });
+            assert_eq!(consumed, 3);
+            assert_eq!(seen, vec![2, 3, 4]);
+            assert!(calls <= 2);
+            assert_eq!(rb.to_vec(), vec![5]);
+
+            rb.push(6);
+            let consumed = rb.dequeue_batch(10, |s| seen.extend_from_slice(s));
+            assert_eq!(consumed, 2);
+            assert_eq!(seen, vec![2, 3, 4, 5, 6]);
+            assert!(rb.is_empty());
+
+            assert_eq!(rb.dequeue_batch(10, |_| panic!("called on empty")), 0);
+        }
+
+        test_dequeue_batch(AllocRingBuffer::new(4));
+        test_dequeue_batch(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_add_assign() {
+        fn test_add_assign<B>(mut rb: B)
+        where
+            B: RingBuffer<i32>
+                + AddAssign<i32>
+                + for<'a> AddAssign<&'a [i32]>
+                + AddAssign<Vec<i32>>,
+        {
+            rb += 1;
+            rb += &[2, 3][..];
+            rb += vec![4, 5];
+            assert_eq!(rb.to_vec(), vec![2, 3, 4, 5]);
+        }
+
+        test_add_assign(AllocRingBuffer::new(4));
+        test_add_assign(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb += 1;
+        rb += &[2, 3][..];
+        rb += vec![4, 5];
+        assert_eq!(rb.to_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn run_test_const_generic_parity() {
+        // the whole RingBuffer surface is available on all buffers, run the same
+        // assertions against each of them.
+        fn test_parity(mut rb: impl RingBuffer<i32>) {
+            rb.extend(0..6);
+            assert_eq!(rb.to_vec(), vec![2, 3, 4, 5]);
+            assert_eq!(rb.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+
+            rb.iter_mut().for_each(|i| *i += 10);
+            assert_eq!(rb[0], 12);
+            rb[3] = 0;
+            assert_eq!(rb.get_signed(-1), Some(&0));
+
+            assert_eq!(rb.drain().collect::<Vec<_>>(), vec![12, 13, 14, 0]);
+            assert!(rb.is_empty());
+
+            let mut i = 0;
+            rb.fill_with(|| {
+                i += 1;
+                i
+            });
+            assert_eq!(rb.to_vec(), vec![1, 2, 3, 4]);
+        }
+
+        test_parity(AllocRingBuffer::new(4));
+        test_parity(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_eq_different_wrap() {
+        fn test_eq_different_wrap<B: RingBuffer<i32> + PartialEq + Debug>(mut a: B, mut b: B) {
+            for _ in 0..7 {
+                b.push(-1);
+                b.skip();
+            }
+
+            a.extend(0..4);
+            b.extend(0..4);
+            assert_ne!(a.as_slices().0.len(), b.as_slices().0.len());
+            assert_eq!(a, b);
+
+            a.push(10);
+            b.push(11);
+            assert_ne!(a, b);
+
+            b.skip();
+            assert_ne!(a, b);
+        }
+
+        test_eq_different_wrap(AllocRingBuffer::new(5), AllocRingBuffer::new(5));
+        test_eq_different_wrap(
+            ConstGenericRingBuffer::<i32, 5>::new(),
+            ConstGenericRingBuffer::<i32, 5>::new(),
+        );
+    }
+
+    #[test]
+    fn run_test_truncate_front() {
+        fn test_truncate_front(mut rb: impl RingBuffer<i32>) {
+            rb.extend(0..7);
+            assert_eq!(rb.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            rb.truncate_front(10);
+            assert_eq!(rb.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            rb.truncate_front(2);
+            assert_eq!(rb.to_vec(), vec![5, 6]);
+
+            rb.push(7);
+            assert_eq!(rb.to_vec(), vec![5, 6, 7]);
+
+            rb.truncate_front(0);
+            assert!(rb.is_empty());
+        }
+
+        test_truncate_front(AllocRingBuffer::new(5));
+        test_truncate_front(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut rb = GrowableAllocRingBuffer::from([0, 1, 2, 3]);
+        rb.truncate_front(1);
+        assert_eq!(rb.to_vec(), vec![3]);
+    }
+
+    #[test]
+    fn run_test_frequencies() {
+        fn test_frequencies(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.frequencies().is_empty());
+
+            rb.extend([9, 9, 1, 2, 1, 3, 2, 1]);
+            let freqs = rb.frequencies();
+            assert_eq!(
+                freqs.into_iter().collect::<Vec<_>>(),
+                vec![(1, 3), (2, 2), (3, 1)]
+            );
+
+            #[cfg(feature = "std")]
+            {
+                let freqs: std::collections::HashMap<_, _> = rb.hash_frequencies();
+                assert_eq!(freqs.len(), 3);
+                assert_eq!(freqs[&1], 3);
+                assert_eq!(freqs[&2], 2);
+            }
+        }
+
+        test_frequencies(AllocRingBuffer::new(6));
+        test_frequencies(ConstGenericRingBuffer::<i32, 6>::new());
+    }
+
+    #[test]
+    fn run_test_index_parity() {
+        fn test_index_parity(mut b: impl RingBuffer<i32>) {
+            b.extend([1, 2, 3]);
+
+            assert_eq!(b[1], 2);
+            assert_eq!(b[4], 2);
+            b[4] = 5;
+            assert_eq!(b.to_vec(), vec![1, 5, 3]);
+
+            *b.get_mut_signed(-1).unwrap() = 6;
+            *b.get_mut_signed(-6).unwrap() = 7;
+            assert_eq!(b.to_vec(), vec![7, 5, 6]);
+            assert_eq!(b.back_mut(), Some(&mut 6));
+        }
+
+        test_index_parity(AllocRingBuffer::new(3));
+        test_index_parity(GrowableAllocRingBuffer::with_capacity(3));
+        test_index_parity(ConstGenericRingBuffer::<i32, 3>::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_empty_growable() {
+        let b = GrowableAllocRingBuffer::<i32>::new();
+        let _ = b[0];
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_mut_empty_growable() {
+        let mut b = GrowableAllocRingBuffer::<i32>::new();
+        b[0] = 1;
+    }
+
+    #[test]
+    fn run_test_extend_until_full() {
+        fn test_extend_until_full(mut rb: impl RingBuffer<i32>) {
+            rb.push(0);
+            let rest = rb.extend_until_full(1..10);
+            assert_eq!(rb.to_vec(), vec![0, 1, 2, 3]);
+            assert_eq!(rest.collect::<Vec<_>>(), vec![4, 5, 6, 7, 8, 9]);
+
+            let mut rest = rb.extend_until_full(10..12);
+            assert_eq!(rest.next(), Some(10));
+            assert_eq!(rb.to_vec(), vec![0, 1, 2, 3]);
+
+            let _ = rb.dequeue();
+            let rest = rb.extend_until_full(vec![4]);
+            assert_eq!(rest.count(), 0);
+            assert_eq!(rb.to_vec(), vec![1, 2, 3, 4]);
+        }
+
+        test_extend_until_full(AllocRingBuffer::new(4));
+        test_extend_until_full(GrowableAllocRingBuffer::with_capacity(4));
+        test_extend_until_full(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_fill_with_indexed() {
+        fn test_fill_with_indexed(mut rb: impl RingBuffer<f32>) {
+            rb.push(10.0);
+            rb.fill_with_indexed(|i| i as f32 * 0.25);
+            assert_eq!(rb.to_vec(), vec![0.0, 0.25, 0.5, 0.75]);
+            assert_eq!(rb.dequeue(), Some(0.0));
+        }
+
+        test_fill_with_indexed(AllocRingBuffer::new(4));
+        test_fill_with_indexed(GrowableAllocRingBuffer::with_capacity(4));
+        test_fill_with_indexed(ConstGenericRingBuffer::<f32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_fill_with_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        fn test_fill_with_panic(mut rb: impl RingBuffer<Rc<()>>) {
+            let value = Rc::new(());
+            let result = catch_unwind(AssertUnwindSafe(|| {
+                rb.fill_with_indexed(|i| {
+                    assert!(i < 2);
+                    value.clone()
+                });
+            }));
+
+            assert!(result.is_err());
+            assert_eq!(rb.len(), 2);
+            assert_eq!(Rc::strong_count(&value), 3);
+
+            drop(rb);
+            assert_eq!(Rc::strong_count(&value), 1);
+        }
+
+        test_fill_with_panic(AllocRingBuffer::new(4));
+        test_fill_with_panic(GrowableAllocRingBuffer::with_capacity(4));
+        test_fill_with_panic(ConstGenericRingBuffer::<_, 4>::new());
+    }
+
+    #[test]
+    fn run_test_capacity_is_power_of_two() {
+        assert!(AllocRingBuffer::<i32>::new(8).capacity_is_power_of_two());
+        assert!(!AllocRingBuffer::<i32>::new(5).capacity_is_power_of_two());
+        assert!(ConstGenericRingBuffer::<i32, 4>::new().capacity_is_power_of_two());
+        assert!(!ConstGenericRingBuffer::<i32, 3>::new().capacity_is_power_of_two());
+        assert!(GrowableAllocRingBuffer::<i32>::with_capacity(16).capacity_is_power_of_two());
+    }
+
+    #[test]
+    fn run_test_push_coalesced() {
+        fn test_push_coalesced(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.push_coalesced(1));
+            assert!(!rb.push_coalesced(1));
+            assert!(!rb.push_coalesced(1));
+            assert!(rb.push_coalesced(2));
+            assert!(rb.push_coalesced(1));
+            assert!(!rb.push_coalesced(1));
+            assert!(rb.push_coalesced(3));
+            assert!(!rb.push_coalesced(3));
+
+            assert_eq!(rb.to_vec(), vec![1, 2, 1, 3]);
+        }
+
+        test_push_coalesced(AllocRingBuffer::new(4));
+        test_push_coalesced(GrowableAllocRingBuffer::new());
+        test_push_coalesced(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_sum_into() {
+        fn test_sum_into(mut rb: impl RingBuffer<u8>) {
+            assert_eq!(rb.sum_into::<u64>(), 0);
+            assert_eq!(rb.product_into::<u64>(), 1);
+
+            rb.extend([255, 255, 255, 2]);
+            assert_eq!(rb.sum_into::<u64>(), 767);
+            assert_eq!(rb.product_into::<u64>(), 255 * 255 * 255 * 2);
+            assert_eq!(rb.sum_into::<f64>(), 767.0);
+        }
+
+        test_sum_into(AllocRingBuffer::new(4));
+        test_sum_into(GrowableAllocRingBuffer::new());
+        test_sum_into(ConstGenericRingBuffer::<u8, 4>::new());
+    }
+
+    #[test]
+    fn run_test_try_get() {
+        fn test_try_get(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.try_get(0), Err(IndexError { index: 0, len: 0 }));
+            assert_eq!(rb.try_get(-1), Err(IndexError { index: -1, len: 0 }));
+
+            rb.extend([1, 2, 3]);
+            assert_eq!(rb.try_get(0), Ok(&1));
+            assert_eq!(rb.try_get(2), Ok(&3));
+            assert_eq!(rb.try_get(-1), Ok(&3));
+            assert_eq!(rb.try_get(-3), Ok(&1));
+            assert_eq!(rb.try_get(3), Err(IndexError { index: 3, len: 3 }));
+            assert_eq!(rb.try_get(-4), Err(IndexError { index: -4, len: 3 }));
+            assert_eq!(
+                rb.try_get(isize::MIN),
+                Err(IndexError {
+                    index: isize::MIN,
+                    len: 3
+                })
+            );
+        }
+
+        test_try_get(AllocRingBuffer::new(8));
+        test_try_get(GrowableAllocRingBuffer::new());
+        test_try_get(ConstGenericRingBuffer::<i32, 8>::new());
+    }
+
+    #[test]
+    fn test_index_error_display() {
+        use std::string::ToString;
+
+        let err = IndexError { index: -5, len: 2 };
+        assert_eq!(
+            err.to_string(),
+            "index -5 is out of range for a ringbuffer of length 2"
+        );
+    }
+
+    #[test]
+    fn run_test_diff() {
+        fn test_diff<RB: RingBuffer<i32>>(mut a: RB, mut b: RB) {
+            assert_eq!(a.diff(&b).count(), 0);
+
+            a.extend(0..8);
+            b.extend([9, 9, 9, 0, 1, 2, 7, 4, 5, 6, 9]);
+            assert_eq!(
+                a.diff(&b).collect::<Vec<_>>(),
+                vec![(0, &3, &7), (4, &7, &9)]
+            );
+            assert_eq!(a.diff(&a).count(), 0);
+
+            let _ = b.dequeue();
+            assert_eq!(a.diff(&b).count(), 4);
+        }
+
+        test_diff(AllocRingBuffer::new(5), AllocRingBuffer::new(5));
+        test_diff(
+            ConstGenericRingBuffer::<i32, 5>::new(),
+            ConstGenericRingBuffer::<i32, 5>::new(),
+        );
+
+        let a = GrowableAllocRingBuffer::from([1, 2, 3]);
+        let b = GrowableAllocRingBuffer::from([1, 2, 4, 5]);
+        assert_eq!(a.diff(&b).collect::<Vec<_>>(), vec![(2, &3, &4)]);
+    }
+
+    #[test]
+    fn run_test_map_in_place() {
+        use std::string::String;
+
+        fn test_map_in_place(mut rb: impl RingBuffer<String>) {
+            rb.map_in_place(|_| unreachable!());
+
+            for s in ["a", "b", "c", "d", "e"] {
+                rb.push(String::from(s));
+            }
+            rb.map_in_place(|s| s.repeat(2));
+            assert_eq!(rb.to_vec(), vec!["bb", "cc", "dd", "ee"]);
+
+            let _ = rb.dequeue();
+            rb.map_in_place(|mut s| {
+                s.push('!');
+                s
+            });
+            assert_eq!(rb.to_vec(), vec!["cc!", "dd!", "ee!"]);
+        }
+
+        test_map_in_place(AllocRingBuffer::new(4));
+        test_map_in_place(ConstGenericRingBuffer::<String, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::from([String::from("x"), String::from("y")]);
+        rb.map_in_place(|s| s + "z");
+        assert_eq!(rb.to_vec(), vec!["xz", "yz"]);
+    }
+
+    #[test]
+    fn run_test_map_in_place_keeps_readers() {
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend([1, 2, 3]);
+        let mut reader = rb.reader();
+
+        rb.map_in_place(|x| x * 10);
+        assert_eq!(rb.to_vec(), vec![10, 20, 30]);
+        assert_eq!(rb.write_generation(), 3);
+        assert_eq!(reader.next(&rb), None);
+        rb.push(4);
+        assert_eq!(reader.next(&rb), Some(&4));
+
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.extend([1, 2, 3, 4, 5]);
+        let mut reader = rb.reader();
+
+        rb.map_in_place(|x| x * 10);
+        assert_eq!(rb.to_vec(), vec![20, 30, 40, 50]);
+        assert_eq!(rb.write_generation(), 5);
+        assert_eq!(reader.next(&rb), None);
+    }
+
+    #[test]
+    fn run_test_map_in_place_panic() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let value = Rc::new(());
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend(core::iter::repeat(value.clone()).take(4));
+
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            rb.map_in_place(|v| {
+                calls += 1;
+                assert!(calls < 3);
+                v
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(rb.len(), 3);
+        assert_eq!(Rc::strong_count(&value), 4);
+        drop(rb);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn run_test_push_all_returning_evicted() {
+        fn test_push_all_returning_evicted(mut rb: impl RingBuffer<i32>) {
+            let evicted = rb.push_all_returning_evicted(0..4);
+            assert!(evicted.is_empty());
+
+            let evicted = rb.push_all_returning_evicted(4..8);
+            assert_eq!(evicted, vec![0, 1, 2, 3]);
+            assert_eq!(rb.to_vec(), vec![4, 5, 6, 7]);
+        }
+
+        test_push_all_returning_evicted(AllocRingBuffer::new(4));
+        test_push_all_returning_evicted(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        assert!(rb.push_all_returning_evicted(0..8).is_empty());
+        assert_eq!(rb.len(), 8);
+    }
+
+    #[test]
+    fn run_test_pairs() {
+        fn test_pairs(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.pairs().count(), 0);
+            rb.push(0);
+            assert_eq!(rb.pairs().count(), 0);
+
+            // wrap around the end of the underlying storage
+            rb.extend(1..7);
+            assert_eq!(
+                rb.pairs().collect::<Vec<_>>(),
+                vec![(&3, &4), (&4, &5), (&5, &6)]
+            );
+        }
+
+        test_pairs(AllocRingBuffer::new(4));
+        test_pairs(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let rb = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(rb.pairs().collect::<Vec<_>>(), vec![(&1, &2), (&2, &3)]);
+    }
+
+    #[test]
+    fn run_test_get_or_push_with() {
+        fn test_get_or_push_with(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(*rb.get_or_push_with(|| 7), 7);
+            assert_eq!(rb.len(), 1);
+
+            *rb.get_or_push_with(|| unreachable!()) += 1;
+            assert_eq!(rb.to_vec(), vec![8]);
+
+            rb.push(2);
+            *rb.get_or_push_with(|| unreachable!()) *= 10;
+            assert_eq!(rb.to_vec(), vec![8, 20]);
+        }
+
+        test_get_or_push_with(AllocRingBuffer::new(2));
+        test_get_or_push_with(GrowableAllocRingBuffer::new());
+        test_get_or_push_with(ConstGenericRingBuffer::<i32, 2>::new());
+    }
+
+    #[test]
+    fn run_test_fill_ratio() {
+        fn test_fill_ratio(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.fill_ratio(), 0.0);
+            assert!(rb.is_at_least_full(0.0));
+            assert!(rb.is_at_least_full(-1.0));
+            assert!(!rb.is_at_least_full(0.1));
+
+            rb.extend(0..5);
+            assert_eq!(rb.fill_ratio(), 0.5);
+            assert!(rb.is_at_least_full(0.5));
+            assert!(!rb.is_at_least_full(0.6));
+
+            rb.extend(0..8);
+            assert_eq!(rb.fill_ratio(), 1.0);
+            assert!(rb.is_at_least_full(1.0));
+            assert!(rb.is_at_least_full(2.0));
+        }
+
+        test_fill_ratio(AllocRingBuffer::new(10));
+        test_fill_ratio(ConstGenericRingBuffer::<i32, 10>::new());
+
+        let mut rb = GrowableAllocRingBuffer::<i32>::new();
+        assert_eq!(rb.fill_ratio(), 1.0);
+        rb.push(1);
+        assert!(rb.fill_ratio() > 0.0);
+    }
+
+    #[test]
+    fn run_test_snapshot_arc() {
+        fn test_snapshot_arc(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.snapshot_arc().is_empty());
+
+            rb.extend(0..6);
+            let snapshot = rb.snapshot_arc();
+            let shared = snapshot.clone();
+            let contents = rb.to_vec();
+            assert_eq!(&snapshot[..], &contents[..]);
+
+            // the snapshot is not affected by changes to the buffer
+            rb.push(6);
+            assert_eq!(&shared[..], &contents[..]);
+        }
+
+        test_snapshot_arc(AllocRingBuffer::new(4));
+        test_snapshot_arc(GrowableAllocRingBuffer::with_capacity(4));
+        test_snapshot_arc(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_iter_count_last() {
+        fn test_iter_count_last(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.iter().count(), 0);
+            assert_eq!(rb.iter().last(), None);
+
+            // wrap around the end of the underlying storage
+            rb.extend(0..7);
+            let naive_count = rb.iter().fold(0, |n, _| n + 1);
+            let naive_last = rb.iter().fold(None, |_, x| Some(x));
+            assert_eq!(rb.iter().count(), naive_count);
+            assert_eq!(rb.iter().last(), naive_last);
+            assert_eq!(rb.iter().last(), Some(&6));
+
+            let mut it = rb.iter();
+            let _ = it.next();
+            let _ = it.next_back();
+            assert_eq!(it.count(), naive_count - 2);
+
+            let mut it = rb.iter();
+            let _ = it.next();
+            let _ = it.next_back();
+            assert_eq!(it.last(), Some(&5));
+
+            let mut it = rb.iter();
+            while it.next().is_some() {}
+            assert_eq!(it.last(), None);
+        }
+
+        test_iter_count_last(AllocRingBuffer::new(4));
+        test_iter_count_last(GrowableAllocRingBuffer::with_capacity(4));
+        test_iter_count_last(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_push_with_policy() {
+        /// Evicts the newest element, but only on every other push onto a full buffer.
+        struct EveryOtherNewest(bool);
+
+        impl crate::OverwritePolicy<i32> for EveryOtherNewest {
+            fn evict(&mut self, (a, b): (&[i32], &[i32]), _: &i32) -> Option<usize> {
+                self.0 = !self.0;
+                self.0.then(|| a.len() + b.len() - 1)
+            }
+        }
+
+        fn test_push_with_policy(mut rb: impl RingBuffer<i32>) {
+            let mut policy = EveryOtherNewest(false);
+            for i in 0..3 {
+                assert!(rb.push_with_policy(i, &mut policy));
+            }
+            assert!(rb.push_with_policy(3, &mut policy));
+            assert!(!rb.push_with_policy(4, &mut policy));
+            assert!(rb.push_with_policy(5, &mut policy));
+            assert_eq!(rb.to_vec(), vec![0, 1, 5]);
+
+            for i in 6..10 {
+                assert!(rb.push_with_policy(i, &mut crate::OverwriteOldest));
+            }
+            assert_eq!(rb.to_vec(), vec![7, 8, 9]);
+        }
+
+        test_push_with_policy(AllocRingBuffer::new(3));
+        test_push_with_policy(ConstGenericRingBuffer::<i32, 3>::new());
+
+        // the growable buffer grows instead, without asking the policy
+        let mut rb = GrowableAllocRingBuffer::from(vec![1, 2, 3, 0]);
+        let mut policy = EveryOtherNewest(false);
+        assert!(rb.push_with_policy(9, &mut policy));
+        assert!(rb.push_with_policy(10, &mut policy));
+        assert_eq!(rb.to_vec(), vec![1, 2, 3, 0, 9, 10]);
+        assert!(!policy.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "evicted index out of bounds")]
+    fn run_test_push_with_policy_out_of_bounds() {
+        struct OutOfBounds;
+
+        impl crate::OverwritePolicy<i32> for OutOfBounds {
+            fn evict(&mut self, _: (&[i32], &[i32]), _: &i32) -> Option<usize> {
+                Some(10)
+            }
+        }
+
+        let mut rb = AllocRingBuffer::from([1, 2]);
+        let _ = rb.push_with_policy(3, &mut OutOfBounds);
+    }
+
+    #[test]
+    fn run_test_extend_reporting() {
+        fn test_extend_reporting(mut rb: impl RingBuffer<i32>) {
+            let report = rb.extend_reporting(0..3);
+            assert_eq!(
+                report,
+                ExtendReport {
+                    consumed: 3,
+                    evicted: 0,
+                    final_len: 3
+                }
+            );
+
+            let report = rb.extend_reporting(3..12);
+            assert_eq!(
+                report,
+                ExtendReport {
+                    consumed: 9,
+                    evicted: 7,
+                    final_len: 5
+                }
+            );
+            assert_eq!(rb.to_vec(), vec![7, 8, 9, 10, 11]);
+        }
+
+        test_extend_reporting(AllocRingBuffer::new(5));
+        test_extend_reporting(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut rb = GrowableAllocRingBuffer::with_capacity(2);
+        let report = rb.extend_reporting(0..5);
+        assert_eq!(report.evicted, 0);
+        assert_eq!(report.final_len, 5);
+    }
+
+    #[test]
+    fn run_test_index_of() {
+        fn test_index_of(mut rb: impl RingBuffer<i32>) {
+            // wrap around the end of the underlying storage
+            rb.extend(0..7);
+
+            for (i, elem) in rb.iter().enumerate() {
+                assert_eq!(rb.index_of(elem), Some(i));
+            }
+
+            let value = 5;
+            assert_eq!(rb.index_of(&value), None);
+
+            let elem: *const i32 = rb.iter().last().unwrap();
+            let _ = rb.dequeue();
+            // the element is still in the buffer, but its index changed
+            assert_eq!(rb.index_of(unsafe { &*elem }), Some(rb.len() - 1));
+        }
+
+        test_index_of(AllocRingBuffer::new(4));
+        test_index_of(GrowableAllocRingBuffer::with_capacity(4));
+        test_index_of(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let rb = ConstGenericRingBuffer::<(), 2>::from([(), ()]);
+        assert_eq!(rb.index_of(&rb[0]), None);
+    }
+
+    #[test]
+    fn run_test_iter_rev() {
+        fn test_iter_rev(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.iter_rev().next(), None);
+
+            // wrap around the end of the underlying storage
+            rb.extend(0..7);
+            let mut expected = rb.to_vec();
+            expected.reverse();
+            assert_eq!(rb.iter_rev().copied().collect::<Vec<_>>(), expected);
+        }
+
+        test_iter_rev(AllocRingBuffer::new(4));
+        test_iter_rev(GrowableAllocRingBuffer::with_capacity(4));
+        test_iter_rev(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_set_front() {
+        fn test_set_front(mut rb: impl RingBuffer<i32>) {
+            rb.extend(0..6);
+            let before = rb.to_vec();
+
+            rb.set_front(2);
+            assert_eq!(rb.get(0), Some(&before[2]));
+            let mut expected = before.clone();
+            expected.rotate_left(2);
+            assert_eq!(rb.to_vec(), expected);
+
+            rb.set_front(0);
+            assert_eq!(rb.to_vec(), expected);
+        }
+
+        test_set_front(AllocRingBuffer::new(4));
+        test_set_front(GrowableAllocRingBuffer::with_capacity(4));
+        test_set_front(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_set_front_keeps_readers() {
+        fn test_set_front_wrapped(mut rb: impl RingBuffer<i32>) {
+            // not full, and wrapping around the end of the underlying storage
+            rb.extend([0, 0, 0]);
+            for _ in 0..3 {
+                let _ = rb.dequeue();
+            }
+            rb.extend(1..=4);
+            for index in 0..4 {
+                let mut expected = rb.to_vec();
+                expected.rotate_left(index);
+                rb.set_front(index);
+                assert_eq!(rb.to_vec(), expected);
+            }
+        }
+
+        test_set_front_wrapped(AllocRingBuffer::new(6));
+        test_set_front_wrapped(GrowableAllocRingBuffer::with_capacity(6));
+        test_set_front_wrapped(ConstGenericRingBuffer::<i32, 6>::new());
+
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend([10, 20, 30]);
+        let mut reader = rb.reader();
+        rb.set_front(1);
+        assert_eq!(rb.to_vec(), vec![20, 30, 10]);
+        assert_eq!(rb.write_generation(), 3);
+        assert_eq!(reader.next(&rb), None);
+        rb.push(40);
+        assert_eq!(reader.next(&rb), Some(&40));
+
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.extend(0..6);
+        let mut reader = rb.reader();
+        rb.set_front(3);
+        assert_eq!(rb.to_vec(), vec![5, 2, 3, 4]);
+        assert_eq!(rb.write_generation(), 6);
+        assert_eq!(reader.next(&rb), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn run_test_set_front_out_of_bounds() {
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend(0..2);
+        rb.set_front(2);
+    }
+
+    #[test]
+    fn run_test_get_clamped() {
+        fn test_get_clamped(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.get_clamped(0), None);
+            assert_eq!(rb.get_clamped(-1), None);
+
+            rb.extend(0..6);
+            let first = *rb.front().unwrap();
+            let last = *rb.back().unwrap();
+
+            assert_eq!(rb.get_clamped(-1), Some(&first));
+            assert_eq!(rb.get_clamped(isize::MIN), Some(&first));
+            assert_eq!(rb.get_clamped(0), Some(&first));
+            assert_eq!(rb.get_clamped(1), rb.get(1));
+            assert_eq!(rb.get_clamped(rb.len() as isize), Some(&last));
+            assert_eq!(rb.get_clamped(isize::MAX), Some(&last));
+        }
+
+        test_get_clamped(AllocRingBuffer::new(4));
+        test_get_clamped(GrowableAllocRingBuffer::with_capacity(4));
+        test_get_clamped(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_to_array() {
+        fn test_to_array(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.to_array::<0>(), Some([]));
+            assert_eq!(rb.to_array::<1>(), None);
+
+            rb.extend(0..6);
+            assert_eq!(rb.to_array::<4>(), Some([2, 3, 4, 5]));
+            assert_eq!(rb.to_array::<3>(), None);
+            assert_eq!(rb.to_array::<5>(), None);
+        }
+
+        test_to_array(AllocRingBuffer::new(4));
+        test_to_array(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb.extend(0..3);
+        assert_eq!(rb.to_array::<3>(), Some([0, 1, 2]));
+        assert_eq!(rb.to_array::<4>(), None);
+    }
+
+    #[test]
+    fn run_test_is_contiguous() {
+        fn test_is_contiguous(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.is_contiguous());
+
+            rb.extend(0..3);
+            assert!(rb.is_contiguous());
+            assert!(rb.as_slices().1.is_empty());
+
+            // wrap around the end of the underlying storage
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend(3..6);
+            assert_eq!(rb.is_contiguous(), rb.as_slices().1.is_empty());
+            assert!(!rb.is_contiguous());
+
+            rb.clear();
+            assert!(rb.is_contiguous());
+        }
+
+        test_is_contiguous(AllocRingBuffer::new(4));
+        test_is_contiguous(GrowableAllocRingBuffer::with_capacity(4));
+        test_is_contiguous(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_scan_window() {
+        fn test_scan_window(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.scan_window(0, |_, x| *x).is_empty());
+
+            rb.extend(0..6);
+            let prefix_sums = rb.scan_window(0, |sum, x| {
+                *sum += x;
+                *sum
+            });
+            let expected: Vec<_> = rb
+                .to_vec()
+                .iter()
+                .scan(0, |sum, x| {
+                    *sum += x;
+                    Some(*sum)
+                })
+                .collect();
+            assert_eq!(prefix_sums, expected);
+        }
+
+        test_scan_window(AllocRingBuffer::new(4));
+        test_scan_window(GrowableAllocRingBuffer::with_capacity(4));
+        test_scan_window(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_eq_iter() {
+        fn test_eq_iter(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.eq_iter(None));
+            assert!(!rb.eq_iter(Some(1)));
+
+            rb.extend(0..6);
+            assert!(rb.eq_iter(rb.to_vec()));
+            assert!(rb.eq_iter(2..6));
+            assert!(!rb.eq_iter(2..5));
+            assert!(!rb.eq_iter(2..7));
+            assert!(!rb.eq_iter([2, 3, 9, 5]));
+        }
+
+        test_eq_iter(AllocRingBuffer::new(4));
+        test_eq_iter(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb.extend(0..3);
+        assert!(rb.eq_iter(0..3));
+        assert!(!rb.eq_iter(0..2));
+        assert!(!rb.eq_iter(0..4));
+    }
+
+    #[test]
+    fn run_test_eq_iter_short_circuits() {
+        let rb = AllocRingBuffer::from(vec![1, 2, 3]);
+        let mut iter = [1, 5, 3, 4].into_iter();
+        assert!(!rb.eq_iter(&mut iter));
+        assert_eq!(iter.next(), Some(3));
+    }
+
+    #[test]
+    fn run_test_to_growable() {
+        fn test_to_growable(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.to_growable().is_empty());
+
+            rb.extend(0..6);
+            let mut growable = rb.to_growable();
+            assert_eq!(growable.to_vec(), rb.to_vec());
+
+            growable.extend(6..10);
+            assert_eq!(growable.len(), rb.len() + 4);
+            assert_eq!(growable.back(), Some(&9));
+            assert!(rb.eq_iter(growable.iter().copied().take(rb.len())));
+        }
+
+        test_to_growable(AllocRingBuffer::new(4));
+        test_to_growable(GrowableAllocRingBuffer::with_capacity(4));
+        test_to_growable(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_insert_sorted() {
+        fn test_insert_sorted(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.insert_sorted(5), 0);
+
+            // wrap around the end of the underlying storage
+            let _ = rb.dequeue();
+            rb.extend([10, 20, 30]);
+            assert_eq!(rb.insert_sorted(15), 1);
+            assert_eq!(rb.to_vec(), vec![10, 15, 20, 30]);
+
+            // the fixed size buffers evict 10 first, the growable one grows
+            let index = rb.insert_sorted(25);
+            assert_eq!(rb.get(index), Some(&25));
+            assert_eq!(rb.get(index - 1), Some(&20));
+            assert_eq!(rb.insert_sorted(1), 0);
+            assert_eq!(rb.insert_sorted(99), rb.len() - 1);
+
+            let v = rb.to_vec();
+            assert!(v.windows(2).all(|w| w[0] <= w[1]));
+        }
+
+        let mut rb = AllocRingBuffer::new(4);
+        test_insert_sorted(rb.clone());
+        rb.extend([10, 20, 30, 40]);
+        assert_eq!(rb.insert_sorted(20), 1);
+        assert_eq!(rb.to_vec(), vec![20, 20, 30, 40]);
+        test_insert_sorted(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        test_insert_sorted(rb.clone());
+        rb.extend([10, 20, 30, 40]);
+        assert_eq!(rb.insert_sorted(25), 2);
+        assert_eq!(rb.to_vec(), vec![10, 20, 25, 30, 40]);
+    }
+
+    #[test]
+    fn run_test_iter_aged() {
+        fn test_iter_aged(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.iter_aged().next(), None);
+
+            rb.extend(0..6);
+            let aged: Vec<_> = rb.iter_aged().map(|(age, x)| (age, *x)).collect();
+            assert_eq!(aged, vec![(0, 5), (1, 4), (2, 3), (3, 2)]);
+        }
+
+        test_iter_aged(AllocRingBuffer::new(4));
+        test_iter_aged(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb.extend(0..3);
+        let ages: Vec<_> = rb.iter_aged().map(|(age, x)| age + *x as usize).collect();
+        assert_eq!(ages, vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn run_test_replace_contents() {
+        fn test_replace_contents(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.replace_contents(vec![1, 2]).is_empty());
+            assert_eq!(rb.to_vec(), vec![1, 2]);
+
+            rb.extend(3..7);
+            let previous = rb.to_vec();
+            let capacity = rb.capacity();
+            assert_eq!(rb.replace_contents(vec![7, 8, 9]), previous);
+            assert_eq!(rb.to_vec(), vec![7, 8, 9]);
+            assert_eq!(rb.capacity(), capacity);
+
+            assert_eq!(rb.replace_contents(vec![]), vec![7, 8, 9]);
+            assert!(rb.is_empty());
+        }
+
+        test_replace_contents(AllocRingBuffer::new(4));
+        test_replace_contents(GrowableAllocRingBuffer::with_capacity(4));
+        test_replace_contents(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = AllocRingBuffer::new(2);
+        assert!(rb.replace_contents((0..5).collect()).is_empty());
+        assert_eq!(rb.to_vec(), vec![3, 4]);
+    }
+
+    #[test]
+    fn test_next_capacity_power_of_two() {
+        const SIZES: [usize; 5] = [
+            next_capacity_power_of_two(0),
+            next_capacity_power_of_two(1),
+            next_capacity_power_of_two(3),
+            next_capacity_power_of_two(8),
+            next_capacity_power_of_two(1025),
+        ];
+        assert_eq!(SIZES, [1, 1, 4, 8, 2048]);
+
+        for capacity in [1, 3, 8, 1025] {
+            let rb = AllocRingBuffer::<u8>::new(capacity);
+            assert_eq!(rb.buffer_size(), next_capacity_power_of_two(capacity));
+        }
+    }
+
+    #[test]
+    fn run_test_histogram() {
+        fn test_histogram(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.histogram(0, 10, 2), vec![0, 0]);
+
+            rb.extend([-5, 0, 1, 4, 5, 9, 10, 20]);
+            assert_eq!(rb.histogram(0, 10, 2), vec![4, 4]);
+            assert_eq!(rb.histogram(0, 10, 1), vec![8]);
+            assert_eq!(rb.histogram(0, 10, 5).iter().sum::<usize>(), 8);
+        }
+
+        test_histogram(AllocRingBuffer::new(8));
+        test_histogram(GrowableAllocRingBuffer::new());
+        test_histogram(ConstGenericRingBuffer::<i32, 8>::new());
+
+        let rb = AllocRingBuffer::from(vec![0, 2, 2, 5, 7, 8, 9, 9, 9]);
+        assert_eq!(rb.histogram(0, 9, 3), vec![3, 1, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn run_test_histogram_no_buckets() {
+        let _ = AllocRingBuffer::from(vec![1]).histogram(0, 1, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn run_test_histogram_empty_range() {
+        let _ = AllocRingBuffer::from(vec![1]).histogram(1, 1, 2);
+    }
+
+    #[test]
+    fn run_test_extend_decimated() {
+        fn test_extend_decimated(mut rb: impl RingBuffer<i32>) {
+            rb.extend_decimated(0..10, 1);
+            assert_eq!(rb.back(), Some(&9));
+
+            rb.clear();
+            rb.extend_decimated(0..8, 2);
+            assert_eq!(rb.to_vec(), vec![0, 2, 4, 6]);
+
+            rb.clear();
+            let mut phase = 0;
+            for start in (0..20).step_by(4) {
+                rb.extend_decimated_with_phase(start..start + 4, 5, &mut phase);
+            }
+            assert_eq!(rb.to_vec(), vec![0, 5, 10, 15]);
+            assert_eq!(phase, 0);
+        }
+
+        test_extend_decimated(AllocRingBuffer::new(4));
+        test_extend_decimated(GrowableAllocRingBuffer::with_capacity(4));
+        test_extend_decimated(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn run_test_extend_decimated_zero() {
+        AllocRingBuffer::new(4).extend_decimated(0..4, 0);
+    }
+
+    #[test]
+    fn run_test_quartiles() {
+        fn test_quartiles(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.quartiles(), None);
+
+            rb.push(3);
+            assert_eq!(rb.quartiles(), Some((3, 3, 3)));
+
+            rb.extend([8, -2, 5, 1]);
+            assert_eq!(rb.quartiles(), Some((-2, 3, 8)));
+
+            rb.push(4);
+            assert_eq!(rb.quartiles(), Some((-2, 3, 8)));
+            rb.push(9);
+            assert_eq!(rb.quartiles(), Some((-2, 4, 9)));
+        }
+
+        test_quartiles(AllocRingBuffer::new(8));
+        test_quartiles(GrowableAllocRingBuffer::new());
+        test_quartiles(ConstGenericRingBuffer::<i32, 8>::new());
+    }
+
+    #[test]
+    fn run_test_peek_while() {
+        fn test_peek_while(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.peek_while(|_| true).next(), None);
+
+            // wrap around the end of the underlying storage
+            rb.extend([9, 9, 9]);
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend([2, 4, 7]);
+            assert_eq!(rb.peek_while(|x| *x > 5).count(), 1);
+            assert_eq!(rb.peek_while(|x| *x != 7).collect::<Vec<_>>(), [&9, &2, &4]);
+            assert_eq!(rb.peek_while(|_| true).count(), 4);
+            assert_eq!(rb.len(), 4);
+
+            let mut iter = rb.peek_while(|x| *x % 2 == 1);
+            assert_eq!(iter.next(), Some(&9));
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next(), None);
+        }
+
+        test_peek_while(AllocRingBuffer::new(4));
+        test_peek_while(GrowableAllocRingBuffer::with_capacity(4));
+        test_peek_while(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_count_matching() {
+        fn test_count_matching(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.count_matching(|_| true), 0);
+
+            // wrap around the end of the underlying storage
+            rb.extend([0, 0, 0]);
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend([7, 2, 9]);
+            assert_eq!(rb.count_matching(|x| *x > 5), 2);
+            assert_eq!(rb.count_matching(|_| true), rb.len());
+            assert_eq!(rb.count_matching(|x| *x < 0), 0);
+        }
+
+        test_count_matching(AllocRingBuffer::new(4));
+        test_count_matching(GrowableAllocRingBuffer::with_capacity(4));
+        test_count_matching(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_copy_into_aligned() {
+        fn test_copy_into_aligned(mut rb: impl RingBuffer<i32>) {
+            let mut out = [-1; 6];
+            assert_eq!(rb.copy_into_aligned(&mut out), 0);
+            assert_eq!(out, [-1; 6]);
+
+            // wrap around the end of the underlying storage
+            rb.extend(0..6);
+            let expected = rb.to_vec();
+            assert_eq!(rb.copy_into_aligned(&mut out), 4);
+            assert_eq!(out[..4], expected[..]);
+            assert_eq!(out[4..], [-1, -1]);
+
+            let mut out = [-1; 3];
+            assert_eq!(rb.copy_into_aligned(&mut out), 3);
+            assert_eq!(out[..], expected[..3]);
+            assert_eq!(rb.to_vec(), expected);
+        }
+
+        test_copy_into_aligned(AllocRingBuffer::new(4));
+        test_copy_into_aligned(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        rb.extend([0, 0, 0]);
+        let _ = rb.dequeue();
+        let _ = rb.dequeue();
+        rb.extend([1, 2, 3]);
+        let mut out = [0; 3];
+        assert_eq!(rb.copy_into_aligned(&mut out), 3);
+        assert_eq!(out, [0, 1, 2]);
+    }
+
+    #[test]
+    fn run_test_interleave() {
+        fn test_interleave<RB: RingBuffer<i32>>(mut a: RB, mut b: RB) {
+            let res = a.interleave(&b);
+            assert!(res.is_empty());
+            assert_eq!(res.capacity(), 1);
+
+            a.extend(0..6);
+            b.extend([10, 11]);
+            let res = a.interleave(&b);
+            assert_eq!(res.capacity(), a.len() + 2);
+            assert!(res.is_full());
+            assert_eq!(&res.to_vec()[..4], [a[0], 10, a[1], 11]);
+            assert_eq!(res.to_vec()[4..], a.to_vec()[2..]);
+
+            let res = b.interleave(&a);
+            assert_eq!(&res.to_vec()[..4], [10, a[0], 11, a[1]]);
+        }
+
+        test_interleave(AllocRingBuffer::new(4), AllocRingBuffer::new(4));
+        test_interleave(
+            GrowableAllocRingBuffer::new(),
+            GrowableAllocRingBuffer::new(),
+        );
+        test_interleave(
+            ConstGenericRingBuffer::<i32, 4>::new(),
+            ConstGenericRingBuffer::<i32, 4>::new(),
+        );
+    }
+
+    #[test]
+    fn run_test_rotate() {
+        fn test_rotate(mut rb: impl RingBuffer<i32>) {
+            rb.rotate(3);
+            assert!(rb.is_empty());
+
+            rb.extend(0..7);
+            let mut expected: std::collections::VecDeque<_> = rb.to_vec().into();
+            for by in [1, -1, 3, -2, 0, 9, -13, 4, isize::MIN, isize::MAX] {
+                rb.rotate(by);
+                let n = by.unsigned_abs() % expected.len();
+                if by >= 0 {
+                    expected.rotate_left(n);
+                } else {
+                    expected.rotate_right(n);
+                }
+                assert!(rb.eq_iter(expected.iter().copied()));
+            }
+        }
+
+        test_rotate(AllocRingBuffer::new(5));
+        test_rotate(GrowableAllocRingBuffer::with_capacity(5));
+        test_rotate(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut rb = AllocRingBuffer::new(8);
+        rb.extend(1..=5);
+        let mut reader = rb.reader();
+        rb.rotate(-2);
+        rb.rotate(7);
+        assert_eq!(rb.to_vec(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(rb.write_generation(), 5);
+        assert_eq!(reader.next(&rb), None);
+    }
+
+    #[test]
+    fn run_test_extend_unique() {
+        fn test_extend_unique(mut rb: impl RingBuffer<i32>) {
+            rb.extend_unique([1, 2, 1, 2, 3, 3, 1, 4]);
+            assert_eq!(rb.to_vec(), vec![1, 2, 3, 4]);
+
+            rb.extend_unique([5, 4, 2, 5]);
+            let v = rb.to_vec();
+            assert_eq!(v.last(), Some(&5));
+            for (i, x) in v.iter().enumerate() {
+                assert!(!v[i + 1..].contains(x));
+            }
+        }
+
+        test_extend_unique(AllocRingBuffer::new(4));
+        test_extend_unique(GrowableAllocRingBuffer::with_capacity(4));
+        test_extend_unique(ConstGenericRingBuffer::<i32, 4>::new());
+
+        // 1 was overwritten, so it can be pushed again
+        let mut rb = AllocRingBuffer::new(2);
+        rb.extend_unique([1, 2, 3, 1]);
+        assert_eq!(rb.to_vec(), vec![3, 1]);
+    }
+
+    #[test]
+    fn run_test_weighted_mean() {
+        fn test_weighted_mean(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.weighted_mean(&[1.0]), None);
+
+            rb.extend([7, 2, 4, 6, 8]);
+            assert_eq!(rb.weighted_mean(&[]), None);
+            assert_eq!(rb.weighted_mean(&[1.0; 5]), None);
+            assert_eq!(rb.weighted_mean(&[1.0]), Some(8.0));
+            assert_eq!(rb.weighted_mean(&[1.0, 1.0]), Some(7.0));
+            // (2 * 0.25 + 4 * 0.25 + 6 * 0.25 + 8 * 0.25)
+            assert_eq!(rb.weighted_mean(&[0.25; 4]), Some(5.0));
+            // (2 * 1 + 4 * 2 + 6 * 3 + 8 * 4) / 10
+            assert_eq!(rb.weighted_mean(&[1.0, 2.0, 3.0, 4.0]), Some(6.0));
+        }
+
+        test_weighted_mean(AllocRingBuffer::new(4));
+        test_weighted_mean(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb.extend([2, 4, 6, 8]);
+        assert_eq!(rb.weighted_mean(&[1.0, 2.0, 3.0, 4.0]), Some(6.0));
+        assert_eq!(rb.weighted_mean(&[1.0; 5]), None);
+    }
+
+    #[test]
+    fn run_test_drain_reuse() {
+        fn test_drain_reuse(mut rb: impl RingBuffer<i32>) {
+            rb.extend(0..6);
+            let expected = rb.to_vec();
+            let capacity = rb.capacity();
+
+            let mut drain = rb.drain_reuse();
+            assert_eq!(drain.next(), Some(expected[0]));
+            assert_eq!(drain.next(), Some(expected[1]));
+            drop(drain);
+            assert!(rb.is_empty());
+            assert_eq!(rb.capacity(), capacity);
+
+            rb.extend([10, 11]);
+            assert_eq!(rb.to_vec(), vec![10, 11]);
+            assert_eq!(rb.drain_reuse().collect::<Vec<_>>(), vec![10, 11]);
+            assert!(rb.is_empty());
+        }
+
+        test_drain_reuse(AllocRingBuffer::new(4));
+        test_drain_reuse(GrowableAllocRingBuffer::with_capacity(4));
+        test_drain_reuse(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_diffs() {
+        fn test_diffs(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.diffs().is_empty());
+            rb.push(3);
+            assert!(rb.diffs().is_empty());
+
+            // a ramp wrapping around the end of the underlying storage
+            rb.extend((0..6).map(|i| 10 + 3 * i));
+            let diffs = rb.diffs();
+            assert_eq!(diffs.len(), rb.len() - 1);
+            assert!(diffs.is_full());
+            assert!(diffs.iter().all(|d| *d == 3));
+        }
+
+        test_diffs(AllocRingBuffer::new(4));
+        test_diffs(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb.extend([5, 3, 8]);
+        assert_eq!(rb.diffs().to_vec(), vec![-2, 5]);
+    }
+
+    #[test]
+    fn run_test_argmax_argmin() {
+        fn test_argmax_argmin(mut rb: impl RingBuffer<i32>) {
+            assert_eq!(rb.argmax(), None);
+            assert_eq!(rb.argmin(), None);
+
+            // the extremes end up in the wrapped around part of the storage
+            rb.extend([0, 0, 0]);
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend([5, 9, -3]);
+            assert!(!rb.is_contiguous());
+            assert_eq!(rb.argmax(), Some(2));
+            assert_eq!(rb.argmin(), Some(3));
+
+            rb.push(9);
+            assert_eq!(rb.get(rb.argmax().unwrap()), Some(&9));
+            assert_eq!(rb.argmax(), rb.iter().position(|x| *x == 9));
+        }
+
+        test_argmax_argmin(AllocRingBuffer::new(4));
+        test_argmax_argmin(GrowableAllocRingBuffer::with_capacity(4));
+        test_argmax_argmin(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_oldest_newest() {
+        fn test_oldest_newest(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.oldest(2).is_empty());
+            assert!(rb.newest(2).is_empty());
+
+            // wrap around the end of the underlying storage
+            rb.extend([0, 0, 0]);
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend([1, 2, 3]);
+            assert_eq!(rb.oldest(3), vec![&0, &1, &2]);
+            assert_eq!(rb.newest(3), vec![&1, &2, &3]);
+            assert_eq!(rb.oldest(0), Vec::<&i32>::new());
+            assert_eq!(rb.newest(0), Vec::<&i32>::new());
+            assert_eq!(rb.oldest(10), vec![&0, &1, &2, &3]);
+            assert_eq!(rb.newest(10), vec![&0, &1, &2, &3]);
+            assert_eq!(rb.len(), 4);
+        }
+
+        test_oldest_newest(AllocRingBuffer::new(4));
+        test_oldest_newest(GrowableAllocRingBuffer::with_capacity(4));
+        test_oldest_newest(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_iir_step() {
+        fn test_iir_step(mut rb: impl RingBuffer<f64>) {
+            // second order low-pass, compared against the difference equation
+            let b = [0.2, 0.4, 0.2];
+            let a = [-0.5, 0.3];
+            let input = [1.0, 1.0, 0.0, -2.0, 3.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+
+            let mut x_hist = [0.0; 2];
+            let mut y_hist = [0.0; 2];
+            for &x in &input {
+                let expected = b[0] * x + b[1] * x_hist[0] + b[2] * x_hist[1]
+                    - a[0] * y_hist[0]
+                    - a[1] * y_hist[1];
+                x_hist = [x, x_hist[0]];
+                y_hist = [expected, y_hist[0]];
+
+                let y = rb.iir_step(x, &b, &a);
+                assert!((y - expected).abs() < 1e-12, "{} != {}", y, expected);
+            }
+
+            // unit gain at DC: a constant input settles at the same value
+            for _ in 0..100 {
+                let _ = rb.iir_step(1.0, &b, &a);
+            }
+            assert!((rb.iir_step(1.0, &b, &a) - 1.0).abs() < 1e-9);
+        }
+
+        test_iir_step(AllocRingBuffer::new(2));
+        test_iir_step(GrowableAllocRingBuffer::with_capacity(2));
+        test_iir_step(ConstGenericRingBuffer::<f64, 2>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iir_step_capacity_too_small() {
+        let mut rb = ConstGenericRingBuffer::<f64, 1>::new();
+        let _ = rb.iir_step(1.0, &[0.2, 0.4, 0.2], &[-0.5, 0.3]);
+    }
+
+    #[test]
+    fn run_test_for_each_contiguous() {
+        fn test_for_each_contiguous(mut rb: impl RingBuffer<i32>) {
+            let mut calls = 0;
+            rb.for_each_contiguous(|_| calls += 1);
+            assert_eq!(calls, 0);
+
+            rb.extend([1, 2, 3]);
+            let mut calls = 0;
+            rb.for_each_contiguous(|chunk| {
+                assert_eq!(chunk, &[1, 2, 3]);
+                calls += 1;
+            });
+            assert_eq!(calls, 1);
+
+            // wrap around the end of the underlying storage
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend([4, 5, 6]);
+            let expected = if rb.as_slices().1.is_empty() { 1 } else { 2 };
+            let mut calls = 0;
+            let mut seen = Vec::new();
+            rb.for_each_contiguous(|chunk| {
+                seen.extend_from_slice(chunk);
+                calls += 1;
+            });
+            assert_eq!(calls, expected);
+            assert_eq!(seen, vec![3, 4, 5, 6]);
+
+            let mut calls = 0;
+            rb.for_each_contiguous_mut(|chunk| {
+                chunk.iter_mut().for_each(|i| *i *= 10);
+                calls += 1;
+            });
+            assert_eq!(calls, expected);
+            assert_eq!(rb.to_vec(), vec![30, 40, 50, 60]);
+        }
+
+        test_for_each_contiguous(AllocRingBuffer::new(4));
+        test_for_each_contiguous(GrowableAllocRingBuffer::with_capacity(4));
+        test_for_each_contiguous(ConstGenericRingBuffer::<i32, 4>::new());
+
+        // the fixed size buffers are known to wrap here
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend([1, 2, 3, 4, 5]);
+        let mut calls = 0;
+        rb.for_each_contiguous(|_| calls += 1);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn run_test_cycle_newest_to_front() {
+        fn test_cycle_newest_to_front(mut rb: impl RingBuffer<i32>) {
+            rb.cycle_newest_to_front();
+            assert!(rb.is_empty());
+            rb.push(1);
+            rb.cycle_newest_to_front();
+            assert_eq!(rb.to_vec(), vec![1]);
+
+            // wrap around the end of the underlying storage
+            let _ = rb.dequeue();
+            rb.extend([1, 2, 3, 4]);
+
+            let mut order = Vec::new();
+            for _ in 0..4 {
+                rb.cycle_newest_to_front();
+                order.push(rb.to_vec());
+            }
+            assert_eq!(
+                order,
+                vec![
+                    vec![4, 1, 2, 3],
+                    vec![3, 4, 1, 2],
+                    vec![2, 3, 4, 1],
+                    vec![1, 2, 3, 4],
+                ]
+            );
+        }
+
+        test_cycle_newest_to_front(AllocRingBuffer::new(4));
+        test_cycle_newest_to_front(GrowableAllocRingBuffer::with_capacity(4));
+        test_cycle_newest_to_front(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.extend(0..6);
+        let mut reader = rb.reader();
+        rb.cycle_newest_to_front();
+        assert_eq!(rb.to_vec(), vec![5, 2, 3, 4]);
+        assert_eq!(rb.write_generation(), 6);
+        assert_eq!(reader.next(&rb), None);
+    }
+
+    #[test]
+    fn run_test_merge_sorted() {
+        fn test_merge_sorted<RB: RingBuffer<i32>>(mut a: RB, mut b: RB) {
+            // make a wrap around the end of the underlying storage
+            a.extend([0, 0]);
+            let _ = a.dequeue();
+            let _ = a.dequeue();
+            a.extend([1, 3, 3, 8]);
+            b.extend([2, 3, 9]);
+
+            let merged = a.merge_sorted(&b);
+            assert_eq!(merged.capacity(), 7);
+            assert!(merged.is_full());
+            let merged = merged.to_vec();
+            assert!(merged.windows(2).all(|w| w[0] <= w[1]));
+            assert_eq!(merged, vec![1, 2, 3, 3, 3, 8, 9]);
+
+            assert_eq!(b.merge_sorted(&b).to_vec(), vec![2, 2, 3, 3, 9, 9]);
+            a.clear();
+            b.clear();
+            assert!(a.merge_sorted(&b).is_empty());
+        }
+
+        test_merge_sorted(AllocRingBuffer::new(4), AllocRingBuffer::new(4));
+        test_merge_sorted(
+            GrowableAllocRingBuffer::with_capacity(4),
+            GrowableAllocRingBuffer::with_capacity(4),
+        );
+        test_merge_sorted(
+            ConstGenericRingBuffer::<i32, 4>::new(),
+            ConstGenericRingBuffer::<i32, 4>::new(),
+        );
+    }
+
+    #[test]
+    fn run_test_run_length_encode() {
+        fn test_run_length_encode(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.run_length_encode().is_empty());
+
+            // the run of 2s wraps around the end of the underlying storage
+            rb.extend([0, 0]);
+            let _ = rb.dequeue();
+            let _ = rb.dequeue();
+            rb.extend([1, 2, 2, 3]);
+            assert_eq!(rb.run_length_encode(), vec![(1, 1), (2, 2), (3, 1)]);
+
+            let _ = rb.dequeue();
+            rb.push(3);
+            assert_eq!(rb.run_length_encode(), vec![(2, 2), (3, 2)]);
+        }
+
+        test_run_length_encode(AllocRingBuffer::new(4));
+        test_run_length_encode(GrowableAllocRingBuffer::with_capacity(4));
+        test_run_length_encode(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_make_contiguous() {
+        fn test_make_contiguous(mut rb: impl RingBuffer<i32>) {
+            assert!(rb.make_contiguous().is_empty());
+
+            // wrap around the end of the underlying storage
+            rb.extend([0, 0, 0]);
+            for _ in 0..3 {
+                let _ = rb.dequeue();
+            }
+            rb.extend([1, 2, 3, 4]);
+
+            assert_eq!(rb.make_contiguous(), &mut [1, 2, 3, 4]);
+            assert!(rb.is_contiguous());
+            assert_eq!(rb.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+            assert_eq!(rb.to_vec(), vec![1, 2, 3, 4]);
+
+            // already contiguous
+            rb.make_contiguous()[0] = 10;
+            assert_eq!(rb.dequeue(), Some(10));
+            rb.push(5);
+            assert_eq!(rb.to_vec(), vec![2, 3, 4, 5]);
+        }
+
+        test_make_contiguous(AllocRingBuffer::new(4));
+        test_make_contiguous(GrowableAllocRingBuffer::with_capacity(4));
+        test_make_contiguous(ConstGenericRingBuffer::<i32, 4>::new());
+
+        // readers are created after rotating the storage
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend(1..=6);
+        assert_eq!(rb.make_contiguous(), &mut [3, 4, 5, 6]);
+        assert_eq!(rb.write_generation(), 4);
+        let mut reader = rb.reader();
+        rb.push(7);
+        rb.push(8);
+        assert_eq!(reader.next(&rb), Some(&7));
+        assert_eq!(reader.next(&rb), Some(&8));
+        assert_eq!(reader.next(&rb), None);
+
+        // nothing moves when the elements are contiguous already, so readers stay valid
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.extend(1..=3);
+        let mut reader = rb.reader();
+        assert_eq!(rb.make_contiguous(), &mut [1, 2, 3]);
+        rb.push(4);
+        assert_eq!(reader.next(&rb), Some(&4));
+    }
+
+    #[test]
+    fn run_test_autocorrelation() {
+        fn test_autocorrelation(mut rb: impl RingBuffer<f64>) {
+            assert_eq!(rb.autocorrelation(0), None);
+            rb.extend([0.0; 4]);
+            assert_eq!(rb.autocorrelation(0), None);
+
+            // a sine with a period of 8 samples, wrapping around the end of the storage
+            rb.clear();
+            rb.extend([0.0; 5]);
+            for _ in 0..5 {
+                let _ = rb.dequeue();
+            }
+            rb.extend((0..32).map(|i| (f64::from(i) * core::f64::consts::PI / 4.0).sin()));
+
+            assert!((rb.autocorrelation(0).unwrap() - 1.0).abs() < 1e-12);
+            let peak = (1..16)
+                .max_by(|&a, &b| {
+                    let (a, b) = (rb.autocorrelation(a), rb.autocorrelation(b));
+                    a.partial_cmp(&b).unwrap()
+                })
+                .unwrap();
+            assert_eq!(peak, 8);
+            // half a period apart the signal is inverted
+            assert!(rb.autocorrelation(4).unwrap() < -0.5);
+            assert_eq!(rb.autocorrelation(32), None);
+        }
+
+        test_autocorrelation(AllocRingBuffer::new(32));
+        test_autocorrelation(GrowableAllocRingBuffer::with_capacity(32));
+        test_autocorrelation(ConstGenericRingBuffer::<f64, 32>::new());
+    }
+
+    #[test]
+    fn run_test_push_ref() {
+        #[derive(Debug, Default, PartialEq)]
+        struct Event {
+            id: i32,
+            payload: Vec<i32>,
+        }
+
+        fn test_push_ref(mut rb: impl RingBuffer<Event>) {
+            for id in 0..5 {
+                let event = rb.push_ref(Event {
+                    id,
+                    ..Event::default()
+                });
+                event.payload.push(id * 10);
+                event.payload.push(id * 10 + 1);
+            }
+
+            let newest = rb.back().unwrap();
+            assert_eq!(newest.id, 4);
+            assert_eq!(newest.payload, vec![40, 41]);
+            assert!(rb
+                .iter()
+                .all(|e| e.payload == vec![e.id * 10, e.id * 10 + 1]));
+        }
+
+        test_push_ref(AllocRingBuffer::new(3));
+        test_push_ref(GrowableAllocRingBuffer::with_capacity(3));
+        test_push_ref(ConstGenericRingBuffer::<Event, 3>::new());
+    }
 }
@@ -0,0 +1,199 @@
+//! A bounded multi-producer single-consumer queue built on an [`AllocRingBuffer`].
+//!
+//! Any number of [`Sender`]s can push values from different threads, and a single [`Receiver`]
+//! dequeues them. The buffer is protected by a [`Mutex`], which is only held for the duration of
+//! a single push or dequeue.
+//!
+//! Producers never block waiting for space. When the queue is full, there are two choices:
+//! [`Sender::push`] overwrites the oldest value, like [`RingBuffer::push`] does, which is what
+//! you want for keeping a window of the most recent events. [`Sender::try_push`] instead hands
+//! the value back, so no events are lost and the producer decides what to do.
+//!
+//! # Example
+//! ```
+//! use ringbuffer::mpsc;
+//! use std::thread;
+//!
+//! let (sender, mut receiver) = mpsc::channel(16);
+//!
+//! let handles: Vec<_> = (0..4)
+//!     .map(|i| {
+//!         let sender = sender.clone();
+//!         thread::spawn(move || sender.push(i))
+//!     })
+//!     .collect();
+//! for handle in handles {
+//!     handle.join().unwrap();
+//! }
+//!
+//! let mut received: Vec<_> = receiver.drain().collect();
+//! received.sort_unstable();
+//! assert_eq!(received, vec![0, 1, 2, 3]);
+//! ```
+
+use crate::{AllocRingBuffer, RingBuffer};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+/// Creates a queue which holds at most `capacity` values, returning its sending and receiving
+/// halves. The [`Sender`] can be cloned to push from multiple threads.
+///
+/// # Panics
+/// Panics when capacity is zero
+#[must_use]
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Mutex::new(AllocRingBuffer::new(capacity)));
+    (
+        Sender {
+            inner: Arc::clone(&inner),
+        },
+        Receiver { inner },
+    )
+}
+
+/// Locks the buffer. A panic while the lock was held can't leave the buffer in an invalid
+/// state, so a poisoned lock is simply taken over.
+fn lock<T>(inner: &Mutex<AllocRingBuffer<T>>) -> MutexGuard<'_, AllocRingBuffer<T>> {
+    inner.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The sending half of a queue created by [`channel`].
+#[derive(Debug)]
+pub struct Sender<T> {
+    inner: Arc<Mutex<AllocRingBuffer<T>>>,
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Pushes `value` onto the queue. When the queue is full, the oldest value is overwritten.
+    pub fn push(&self, value: T) {
+        lock(&self.inner).push(value);
+    }
+
+    /// Pushes `value` onto the queue if there is space left.
+    ///
+    /// # Errors
+    /// Returns `value` back when the queue is full.
+    pub fn try_push(&self, value: T) -> Result<(), T> {
+        let mut rb = lock(&self.inner);
+        if rb.is_full() {
+            Err(value)
+        } else {
+            rb.push(value);
+            Ok(())
+        }
+    }
+}
+
+/// The receiving half of a queue created by [`channel`]. There is only one per queue.
+#[derive(Debug)]
+pub struct Receiver<T> {
+    inner: Arc<Mutex<AllocRingBuffer<T>>>,
+}
+
+impl<T> Receiver<T> {
+    /// Removes the oldest value from the queue, or returns `None` if it is empty.
+    pub fn dequeue(&mut self) -> Option<T> {
+        lock(&self.inner).dequeue()
+    }
+
+    /// Removes all values currently in the queue, oldest first. The lock is held only while
+    /// taking them out, not while iterating over the result.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> {
+        let values: Vec<T> = lock(&self.inner).drain().collect();
+        values.into_iter()
+    }
+
+    /// Returns the number of values in the queue.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        lock(&self.inner).len()
+    }
+
+    /// Returns true if the queue is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        lock(&self.inner).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::channel;
+    use std::thread;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_stress_no_loss() {
+        const PRODUCERS: usize = 4;
+        const PER_PRODUCER: usize = 10_000;
+
+        let (sender, mut receiver) = channel(64);
+        let handles: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for i in 0..PER_PRODUCER {
+                        let mut value = (producer, i);
+                        while let Err(v) = sender.try_push(value) {
+                            value = v;
+                            thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let mut next = [0; PRODUCERS];
+        let mut received = 0;
+        while received < PRODUCERS * PER_PRODUCER {
+            match receiver.dequeue() {
+                Some((producer, i)) => {
+                    // values of a single producer arrive in order
+                    assert_eq!(next[producer], i);
+                    next[producer] += 1;
+                    received += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(receiver.is_empty());
+    }
+
+    #[test]
+    fn test_push_overwrites() {
+        let (sender, mut receiver) = channel(8);
+        let handles: Vec<_> = (0..4)
+            .map(|producer| {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for i in 0..1000 {
+                        sender.push((producer, i));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(receiver.len(), 8);
+        let values: Vec<_> = receiver.drain().collect();
+        for producer in 0..4 {
+            let ours: Vec<_> = values.iter().filter(|(p, _)| *p == producer).collect();
+            assert!(ours.windows(2).all(|w| w[0].1 < w[1].1));
+        }
+        assert!(receiver.is_empty());
+    }
+}
@@ -0,0 +1,48 @@
+/// Decides what happens when a value is pushed onto a full ringbuffer with
+/// [`push_with_policy`](crate::RingBuffer::push_with_policy).
+///
+/// This turns a ringbuffer into a configurable bounded sampler. For example, a policy which
+/// evicts a random element implements reservoir sampling.
+///
+/// # Example
+/// ```
+/// use ringbuffer::{AllocRingBuffer, OverwritePolicy, RingBuffer};
+///
+/// /// Keeps the largest values seen so far.
+/// struct KeepLargest;
+///
+/// impl OverwritePolicy<i32> for KeepLargest {
+///     fn evict(&mut self, (first, second): (&[i32], &[i32]), value: &i32) -> Option<usize> {
+///         let (index, smallest) = first.iter().chain(second).enumerate().min_by_key(|(_, v)| **v)?;
+///         (smallest < value).then(|| index)
+///     }
+/// }
+///
+/// let mut rb = AllocRingBuffer::new(3);
+/// for value in [5, 1, 8, 3, 9, 2] {
+///     rb.push_with_policy(value, &mut KeepLargest);
+/// }
+/// assert_eq!(rb.to_vec(), vec![5, 8, 9]);
+/// ```
+pub trait OverwritePolicy<T> {
+    /// Called when `value` is pushed while the buffer is full. `buffer` holds the elements
+    /// currently in the buffer, oldest first, split in two slices like
+    /// [`as_slices`](crate::RingBuffer::as_slices) returns them.
+    ///
+    /// Returns the index of the element to evict, counting from the oldest element across both
+    /// slices, after which `value` is pushed as the newest element. Returns `None` to drop
+    /// `value` instead, leaving the buffer unchanged.
+    fn evict(&mut self, buffer: (&[T], &[T]), value: &T) -> Option<usize>;
+}
+
+/// The [`OverwritePolicy`] used by [`push`](crate::RingBuffer::push): always evicts the oldest
+/// element.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OverwriteOldest;
+
+impl<T> OverwritePolicy<T> for OverwriteOldest {
+    #[inline]
+    fn evict(&mut self, _: (&[T], &[T]), _: &T) -> Option<usize> {
+        Some(0)
+    }
+}
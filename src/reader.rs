@@ -0,0 +1,144 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+/// A `Reader` is a cursor into a ringbuffer which remembers which elements it has already seen.
+///
+/// Reading through a `Reader` does not remove elements from the buffer, so many readers can
+/// follow the same buffer independently, each at its own pace, like a broadcast channel.
+/// When the buffer overwrites elements a reader has not seen yet, the reader lags behind.
+///
+/// Readers are created with `reader()` on [`AllocRingBuffer`](crate::AllocRingBuffer) and
+/// [`ConstGenericRingBuffer`](crate::ConstGenericRingBuffer), and only observe elements pushed
+/// after their creation. A reader should be recreated after the buffer is cleared, with `clear`
+/// or `reset`, or after the elements were moved by `align_read_to` or `make_contiguous`.
+///
+/// # Example
+/// ```
+/// use ringbuffer::{AllocRingBuffer, RingBuffer};
+///
+/// let mut buffer = AllocRingBuffer::new(2);
+/// let mut reader = buffer.reader();
+///
+/// buffer.push(1);
+/// assert_eq!(reader.next(&buffer), Some(&1));
+/// assert_eq!(reader.next(&buffer), None);
+///
+/// buffer.push(2);
+/// buffer.push(3);
+/// buffer.push(4);
+///
+/// // 2 was overwritten before the reader saw it
+/// assert_eq!(reader.lagged_by(&buffer), 1);
+/// assert_eq!(reader.next(&buffer), Some(&3));
+/// ```
+pub struct Reader<RB> {
+    // the write position of the first element this reader has not seen yet
+    pub(crate) position: usize,
+    phantom: PhantomData<fn(&RB)>,
+}
+
+impl<RB> Reader<RB> {
+    #[inline]
+    pub(crate) fn new(position: usize) -> Self {
+        Self {
+            position,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The error returned by `read` on a [`Reader`] when elements were overwritten before the
+/// reader saw them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged {
+    /// The number of elements the reader missed.
+    pub missed: usize,
+}
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "reader lagged behind and missed {} elements",
+            self.missed
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Lagged {}
+
+#[cfg(test)]
+mod tests {
+    use super::Lagged;
+    use crate::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer};
+
+    #[test]
+    fn test_reader_alloc() {
+        let mut rb = AllocRingBuffer::new(4);
+        let mut fast = rb.reader();
+
+        rb.extend([1, 2]);
+        let mut slow = rb.reader();
+        assert_eq!(fast.next(&rb), Some(&1));
+        assert_eq!(fast.next(&rb), Some(&2));
+        assert_eq!(fast.next(&rb), None);
+        assert_eq!(slow.next(&rb), None);
+
+        rb.extend([3, 4, 5, 6, 7, 8]);
+        assert_eq!(fast.lagged_by(&rb), 2);
+        assert_eq!(slow.lagged_by(&rb), 2);
+        assert_eq!(fast.next(&rb), Some(&5));
+        assert_eq!(fast.lagged_by(&rb), 0);
+
+        rb.push(9);
+        assert_eq!(slow.lagged_by(&rb), 3);
+        assert_eq!(slow.next(&rb), Some(&6));
+        assert_eq!(fast.next(&rb), Some(&6));
+        assert_eq!(rb.to_vec(), alloc::vec![6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_reader_const_generic() {
+        let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+        let mut fast = rb.reader();
+        rb.push(1);
+        let mut slow = rb.reader();
+
+        assert_eq!(fast.next(&rb), Some(&1));
+        rb.extend([2, 3, 4, 5]);
+        assert_eq!(fast.lagged_by(&rb), 1);
+        assert_eq!(slow.lagged_by(&rb), 1);
+        assert_eq!(slow.next(&rb), Some(&3));
+        assert_eq!(fast.next(&rb), Some(&3));
+        assert_eq!(fast.next(&rb), Some(&4));
+        assert_eq!(fast.next(&rb), Some(&5));
+        assert_eq!(fast.next(&rb), None);
+    }
+
+    #[test]
+    fn test_read_lagged() {
+        let mut rb = AllocRingBuffer::new(4);
+        let mut reader = rb.reader();
+
+        rb.extend([1, 2]);
+        assert_eq!(reader.read(&rb), Ok(Some(&1)));
+
+        rb.extend([3, 4, 5, 6, 7]);
+        assert_eq!(reader.read(&rb), Err(Lagged { missed: 2 }));
+        assert_eq!(reader.read(&rb), Ok(Some(&4)));
+        assert_eq!(reader.read(&rb), Ok(Some(&5)));
+
+        rb.extend([8, 9, 10]);
+        assert_eq!(reader.read(&rb), Err(Lagged { missed: 1 }));
+        assert_eq!(reader.read(&rb), Ok(Some(&7)));
+
+        let mut rb = ConstGenericRingBuffer::<i32, 2>::new();
+        let mut reader = rb.reader();
+        rb.extend([1, 2, 3]);
+        assert_eq!(reader.read(&rb), Err(Lagged { missed: 1 }));
+        assert_eq!(reader.read(&rb), Ok(Some(&2)));
+        assert_eq!(reader.read(&rb), Ok(Some(&3)));
+        assert_eq!(reader.read(&rb), Ok(None));
+    }
+}
@@ -1,9 +1,38 @@
-use core::ops::{Index, IndexMut};
+use core::fmt;
+use core::iter::{Cloned, Enumerate, Product, Rev, Skip, Sum, Zip};
+use core::mem::{self, size_of, size_of_val};
+use core::ops::{Add, Index, IndexMut, Mul, Sub};
+use core::ptr;
+
+use crate::{LastEntry, OverwritePolicy};
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "alloc")]
+use crate::{AllocRingBuffer, GrowableAllocRingBuffer};
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Checks the invariants of the read and write pointers of a ringbuffer in debug builds, when the
+/// `validate` feature is enabled. Compiles to nothing otherwise.
+macro_rules! validate_invariants {
+    ($rb: expr) => {
+        #[cfg(all(feature = "validate", debug_assertions))]
+        {
+            if let Err(e) = $rb.check_invariants() {
+                panic!("{}", e);
+            }
+        }
+    };
+}
 
 /// `RingBuffer` is a trait defining the standard interface for all `RingBuffer`
 /// implementations ([`AllocRingBuffer`](crate::AllocRingBuffer), [`ConstGenericRingBuffer`](crate::ConstGenericRingBuffer))
@@ -18,6 +47,12 @@ use alloc::vec::Vec;
 /// in the safety comment on the next function of the mutable Iterator
 /// implementation, since these safety guarantees are necessary for
 /// [`iter_mut`](RingBuffer::iter_mut) to work
+///
+/// Besides element access, an implementation exposes its storage through
+/// [`as_slices`](RingBuffer::as_slices), [`as_mut_slices`](RingBuffer::as_mut_slices) and
+/// [`make_contiguous`](RingBuffer::make_contiguous). These have no default, since only the
+/// implementation knows how its elements are laid out. The slices must hold exactly the
+/// elements in the buffer, oldest first.
 pub unsafe trait RingBuffer<T>:
     Sized + IntoIterator<Item = T> + Extend<T> + Index<usize, Output = T> + IndexMut<usize>
 {
@@ -55,6 +90,37 @@ pub unsafe trait RingBuffer<T>:
         unsafe { Self::ptr_capacity(self) }
     }
 
+    /// Returns the number of elements which can be pushed before the buffer is full and starts
+    /// overwriting elements, `capacity() - len()`.
+    #[inline]
+    fn spare_capacity_len(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Returns how full the buffer is, as a fraction between 0 and 1. A buffer with a capacity
+    /// of zero is considered full, like in [`is_full`](RingBuffer::is_full).
+    fn fill_ratio(&self) -> f64 {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            1.0
+        } else {
+            self.len() as f64 / capacity as f64
+        }
+    }
+
+    /// Returns true if the buffer is filled to at least `ratio` of its capacity, where `ratio`
+    /// is clamped to lie between 0 and 1. For example, `is_at_least_full(0.8)` checks whether
+    /// the buffer is at least 80% full.
+    fn is_at_least_full(&self, ratio: f64) -> bool {
+        self.fill_ratio() >= ratio.clamp(0.0, 1.0)
+    }
+
+    /// Returns true if the capacity of the buffer is a power of two. In that case the buffer
+    /// size equals the capacity and wrapping an index can be done with a bitmask.
+    fn capacity_is_power_of_two(&self) -> bool {
+        self.capacity().is_power_of_two()
+    }
+
     /// Returns the number of elements allocated for this ringbuffer (can be larger than capacity).
     fn buffer_size(&self) -> usize {
         // Safety: self is a RingBuffer
@@ -83,6 +149,122 @@ pub unsafe trait RingBuffer<T>:
         self.push(value);
     }
 
+    /// Pushes `value` like [`push`](RingBuffer::push) does, and returns a mutable reference to
+    /// it, so it can still be modified after being added.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// *rb.push_ref(1) += 10;
+    /// assert_eq!(rb.to_vec(), vec![11]);
+    /// ```
+    fn push_ref(&mut self, value: T) -> &mut T {
+        self.push(value);
+        self.back_mut()
+            .expect("the buffer can't be empty after a push")
+    }
+
+    /// Pushes `value` onto the buffer, letting `policy` decide what to do when the buffer is
+    /// full: which element to evict, or whether to drop `value` instead. With
+    /// [`OverwriteOldest`](crate::OverwriteOldest) this behaves like [`push`](RingBuffer::push).
+    ///
+    /// Returns true if `value` was added to the buffer.
+    ///
+    /// [`GrowableAllocRingBuffer`](crate::GrowableAllocRingBuffer) grows instead of evicting,
+    /// so it always pushes `value` without consulting `policy`.
+    ///
+    /// # Panics
+    /// Panics when the policy returns an index which is out of bounds
+    fn push_with_policy<P: OverwritePolicy<T>>(&mut self, value: T, policy: &mut P) -> bool {
+        if !self.is_full() {
+            self.push(value);
+            return true;
+        }
+
+        let evict = match policy.evict(self.as_slices(), &value) {
+            Some(evict) => evict,
+            None => return false,
+        };
+        assert!(evict < self.len(), "evicted index out of bounds");
+
+        // move the evicted element to the front, so push overwrites it
+        for i in (1..=evict).rev() {
+            // Safety: i and i - 1 are different indices in bounds, so the pointers don't alias
+            unsafe {
+                let a = Self::ptr_get_mut(self, i).expect("index in bounds");
+                let b = Self::ptr_get_mut(self, i - 1).expect("index in bounds");
+                ptr::swap(a, b);
+            }
+        }
+        self.push(value);
+        true
+    }
+
+    /// Inserts `value` into a buffer which is sorted in ascending order, oldest first, keeping
+    /// it sorted. Returns the index at which `value` was placed. Equal elements keep the order
+    /// in which they were inserted. If the buffer isn't sorted, `value` is placed at an
+    /// unspecified position.
+    ///
+    /// When the buffer is full, this evicts like [`push`](RingBuffer::push) does: the front
+    /// element is dropped, which in a sorted buffer is the smallest one. That happens before
+    /// `value` is placed, so a `value` smaller than all elements is kept at index 0.
+    ///
+    /// This moves the elements after the insertion point, one at a time.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(vec![1, 3, 5]);
+    /// assert_eq!(rb.insert_sorted(4), 1);
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 5]);
+    /// ```
+    fn insert_sorted(&mut self, value: T) -> usize
+    where
+        T: Ord,
+    {
+        self.push(value);
+        let last = self.len() - 1;
+
+        // find the first element greater than the value, which is now the newest element
+        let (mut lo, mut hi) = (0, last);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.get(mid) <= self.get(last) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+
+        // move the value back to its place
+        for i in (lo + 1..=last).rev() {
+            // Safety: i and i - 1 are different indices in bounds, so the pointers don't alias
+            unsafe {
+                let a = Self::ptr_get_mut(self, i).expect("index in bounds");
+                let b = Self::ptr_get_mut(self, i - 1).expect("index in bounds");
+                ptr::swap(a, b);
+            }
+        }
+        lo
+    }
+
+    /// Pushes `value` onto the buffer, unless it is equal to the most recently pushed element.
+    /// This coalesces runs of identical values into a single element.
+    ///
+    /// Returns true if the value was pushed.
+    fn push_coalesced(&mut self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        if self.back() == Some(&value) {
+            false
+        } else {
+            self.push(value);
+            true
+        }
+    }
+
     /// dequeues the top item off the ringbuffer, and moves this item out.
     fn dequeue(&mut self) -> Option<T>;
 
@@ -91,6 +273,7 @@ pub unsafe trait RingBuffer<T>:
     #[inline]
     fn skip(&mut self) {
         let _ = self.dequeue();
+        validate_invariants!(self);
     }
 
     /// Returns an iterator over the elements in the ringbuffer,
@@ -99,160 +282,1613 @@ pub unsafe trait RingBuffer<T>:
     /// ```
     /// use ringbuffer::{AllocRingBuffer, RingBuffer};
     ///
-    /// let mut rb = AllocRingBuffer::new(16);
-    /// for i in 0..8 {
-    ///     rb.push(i);
-    /// }
+    /// let mut rb = AllocRingBuffer::new(16);
+    /// for i in 0..8 {
+    ///     rb.push(i);
+    /// }
+    ///
+    /// assert_eq!(rb.len(), 8);
+    ///
+    /// for i in rb.drain() {
+    ///     // prints the numbers 0 through 8
+    ///     println!("{}", i);
+    /// }
+    ///
+    /// // No elements remain
+    /// assert_eq!(rb.len(), 0);
+    ///
+    /// ```
+    fn drain(&mut self) -> RingBufferDrainingIterator<'_, T, Self> {
+        RingBufferDrainingIterator::new(self)
+    }
+
+    /// Like [`drain`](RingBuffer::drain), but guarantees the state of the buffer afterwards:
+    /// when the returned iterator is dropped, even before it is fully consumed, the remaining
+    /// elements are dropped and the buffer is left empty, with its storage intact and its
+    /// internal pointers reset. This keeps pooled buffers in a known state.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// assert_eq!(rb.drain_reuse().next(), Some(1));
+    /// assert!(rb.is_empty());
+    /// assert_eq!(rb.capacity(), 3);
+    /// ```
+    fn drain_reuse(&mut self) -> RingBufferDrainReuseIterator<'_, T, Self> {
+        RingBufferDrainReuseIterator::new(self)
+    }
+
+    /// Drops the oldest elements until at most `len` elements remain, keeping the `len` most
+    /// recently pushed ones. If the buffer holds `len` elements or less, this is a nop.
+    fn truncate_front(&mut self, len: usize) {
+        while self.len() > len {
+            self.skip();
+        }
+        validate_invariants!(self);
+    }
+
+    /// Pushes all elements from `iter`, like [`extend`](Extend::extend), and returns an
+    /// [`ExtendReport`] with the number of elements consumed and evicted.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, ExtendReport, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// let report = rb.extend_reporting(0..10);
+    /// assert_eq!(report, ExtendReport { consumed: 10, evicted: 6, final_len: 4 });
+    /// ```
+    fn extend_reporting<I: IntoIterator<Item = T>>(&mut self, iter: I) -> ExtendReport {
+        let mut consumed = 0;
+        let mut evicted = 0;
+        for value in iter {
+            let len = self.len();
+            self.push(value);
+            consumed += 1;
+            if self.len() == len {
+                evicted += 1;
+            }
+        }
+
+        ExtendReport {
+            consumed,
+            evicted,
+            final_len: self.len(),
+        }
+    }
+
+    /// Pushes all elements from `iter`, like [`extend`](Extend::extend), but returns the elements
+    /// that were overwritten in the process, oldest first.
+    ///
+    /// A [`GrowableAllocRingBuffer`](crate::GrowableAllocRingBuffer) grows instead of overwriting,
+    /// so for it the returned vector is always empty.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(3);
+    /// rb.push(0);
+    ///
+    /// let evicted = rb.push_all_returning_evicted(1..5);
+    /// assert_eq!(evicted, vec![0, 1]);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn push_all_returning_evicted<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Vec<T> {
+        let mut evicted = Vec::new();
+        for value in iter {
+            if self.is_full() {
+                evicted.extend(self.dequeue());
+            }
+            self.push(value);
+        }
+        evicted
+    }
+
+    /// Replaces the contents of the buffer with the elements of `new`, and returns the previous
+    /// elements, oldest first. The capacity is kept: when `new` holds more elements than fit,
+    /// only the last ones remain, like when pushing them one by one.
+    ///
+    /// This is useful for double buffering, where the returned vector can be reused to compute
+    /// the next window.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// let old = rb.replace_contents(vec![4, 5, 6, 7]);
+    /// assert_eq!(old, vec![1, 2, 3]);
+    /// assert_eq!(rb.to_vec(), vec![5, 6, 7]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn replace_contents(&mut self, new: Vec<T>) -> Vec<T> {
+        let old = self.drain().collect();
+        for value in new {
+            self.push(value);
+        }
+        old
+    }
+
+    /// Pushes the items of `iter` which are not equal to any element in the buffer, so the
+    /// buffer keeps holding unique elements, like a cache of recently seen values. Items are
+    /// compared to the buffer as it is when they are pushed, so duplicates within `iter` are
+    /// skipped too, unless the earlier one was overwritten in the meantime.
+    ///
+    /// Every item is compared with every element using [`contains`](RingBuffer::contains), so
+    /// this takes `O(n * m)` time for `n` elements and `m` items. That is fine for small windows.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend_unique([1, 2, 1, 3, 2]);
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+    /// ```
+    fn extend_unique<I: IntoIterator<Item = T>>(&mut self, iter: I)
+    where
+        T: PartialEq,
+    {
+        for value in iter {
+            if !self.contains(&value) {
+                self.push(value);
+            }
+        }
+    }
+
+    /// Pushes every `keep_every`-th item of `iter`, starting with the first one, and drops the
+    /// items in between. This downsamples a stream while ingesting it.
+    ///
+    /// Every call starts with the first item again. To continue the pattern across calls, use
+    /// [`extend_decimated_with_phase`](RingBuffer::extend_decimated_with_phase).
+    ///
+    /// # Panics
+    /// Panics when `keep_every` is 0
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend_decimated(0..10, 3);
+    /// assert_eq!(rb.to_vec(), vec![0, 3, 6, 9]);
+    /// ```
+    fn extend_decimated<I: IntoIterator<Item = T>>(&mut self, iter: I, keep_every: usize) {
+        let mut phase = 0;
+        self.extend_decimated_with_phase(iter, keep_every, &mut phase);
+    }
+
+    /// Like [`extend_decimated`](RingBuffer::extend_decimated), but continues the pattern of a
+    /// previous call. `phase` is the number of items to drop before the next one is pushed, and
+    /// is updated so it can be passed to the next call. Start with a phase of 0 to push the
+    /// first item.
+    ///
+    /// # Panics
+    /// Panics when `keep_every` is 0
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(8);
+    /// let mut phase = 0;
+    /// rb.extend_decimated_with_phase(0..5, 3, &mut phase);
+    /// rb.extend_decimated_with_phase(5..10, 3, &mut phase);
+    /// assert_eq!(rb.to_vec(), vec![0, 3, 6, 9]);
+    /// assert_eq!(phase, 2);
+    /// ```
+    fn extend_decimated_with_phase<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+        keep_every: usize,
+        phase: &mut usize,
+    ) {
+        assert!(keep_every > 0, "keep_every must be greater than 0");
+
+        for value in iter {
+            if *phase == 0 {
+                self.push(value);
+                *phase = keep_every;
+            }
+            *phase -= 1;
+        }
+    }
+
+    /// Pushes elements from `iter` until the ringbuffer is full, without overwriting anything.
+    /// Returns the iterator with the elements that did not fit.
+    ///
+    /// Note that a [`GrowableAllocRingBuffer`](crate::GrowableAllocRingBuffer) is considered full
+    /// when its current capacity is reached, so this never makes it grow.
+    fn extend_until_full<I: IntoIterator<Item = T>>(&mut self, iter: I) -> I::IntoIter {
+        let mut iter = iter.into_iter();
+        while !self.is_full() {
+            match iter.next() {
+                Some(value) => self.push(value),
+                None => break,
+            }
+        }
+        iter
+    }
+
+    /// Replaces every element with the result of calling `f` on it, keeping the order.
+    /// Unlike [`iter_mut`](RingBuffer::iter_mut), `f` takes ownership of the elements.
+    ///
+    /// The elements are transformed where they are stored, so the read and write pointers
+    /// don't move: a [`Reader`](crate::Reader) doesn't see the new values as new elements.
+    ///
+    /// If `f` panics, the element passed to it is dropped, and the buffer is left holding
+    /// the remaining elements in some valid but unspecified order.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.push(String::from("a"));
+    /// rb.push(String::from("b"));
+    ///
+    /// rb.map_in_place(|s| s + "!");
+    /// assert_eq!(rb.to_vec(), vec!["a!", "b!"]);
+    /// ```
+    fn map_in_place<F: FnMut(T) -> T>(&mut self, mut f: F) {
+        let rb: *mut Self = self;
+        // Safety: the slices are only kept as raw parts, so the guard can use rb again
+        let (first, second) = unsafe { (*rb).as_mut_slices() };
+        let halves = [
+            (first.as_mut_ptr(), first.len()),
+            (second.as_mut_ptr(), second.len()),
+        ];
+
+        for (start, len) in halves {
+            for i in 0..len {
+                // Safety: slot is in bounds and initialized. While its element is moved out,
+                // the guard fills the hole if f panics, so it is never dropped twice.
+                unsafe {
+                    let slot = start.add(i);
+                    let guard = MapInPlaceGuard { rb, hole: slot };
+                    let value = f(ptr::read(slot));
+                    mem::forget(guard);
+                    ptr::write(slot, value);
+                }
+            }
+        }
+    }
+
+    /// Rotates the buffer so the element currently at `index` becomes the front, at index 0.
+    /// The elements before it are moved to the back, keeping their order.
+    ///
+    /// The elements are moved between the slots they are stored in, which takes time
+    /// proportional to the length; only [`GrowableAllocRingBuffer`](crate::GrowableAllocRingBuffer)
+    /// can do it by moving fewer elements. The read and write pointers don't move, so to a
+    /// [`Reader`](crate::Reader) rotating isn't a write: one which is up to date stays up to
+    /// date, and [`write_generation`](crate::AllocRingBuffer::write_generation) is unchanged.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(vec![1, 2, 3, 4]);
+    /// rb.set_front(1);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4, 1]);
+    /// ```
+    fn set_front(&mut self, index: usize) {
+        let len = self.len();
+        assert!(index < len, "index out of bounds");
+        if index == 0 {
+            return;
+        }
+
+        let (first, second) = self.as_mut_slices();
+        if second.is_empty() {
+            first.rotate_left(index);
+        } else {
+            // rotating left is reversing both parts, and then the whole
+            reverse_range(self, 0, index);
+            reverse_range(self, index, len);
+            reverse_range(self, 0, len);
+        }
+        validate_invariants!(self);
+    }
+
+    /// Rotates the elements by `by` places, reduced modulo the length. A positive amount rotates
+    /// to the left, so the element at index `by` becomes the front, like
+    /// `VecDeque::rotate_left`. A negative amount rotates to the right. Does nothing when the
+    /// buffer is empty.
+    ///
+    /// This is [`set_front`](RingBuffer::set_front) with a signed amount: it takes time
+    /// proportional to the length, and leaves the read and write pointers where they are, so
+    /// [`Reader`](crate::Reader)s don't see the rotated elements as new ones.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(vec![1, 2, 3, 4]);
+    /// rb.rotate(1);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4, 1]);
+    /// rb.rotate(-2);
+    /// assert_eq!(rb.to_vec(), vec![4, 1, 2, 3]);
+    /// ```
+    fn rotate(&mut self, by: isize) {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+
+        // a rotation to the right is a rotation to the left by the remaining places
+        let index = by.rem_euclid(len as isize) as usize;
+        if index != 0 {
+            self.set_front(index);
+        }
+    }
+
+    /// Moves the newest element to the front, so it becomes the oldest element, and shifts the
+    /// others one place towards the back. This is `rotate(-1)`, named for round-robin
+    /// scheduling. Does nothing when there are fewer than two elements.
+    ///
+    /// [`GrowableAllocRingBuffer`](crate::GrowableAllocRingBuffer) does this in constant time.
+    /// The other buffers rotate their elements in place like
+    /// [`set_front`](RingBuffer::set_front) does, in time proportional to the length, without
+    /// moving their read and write pointers.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// rb.cycle_newest_to_front();
+    /// assert_eq!(rb.to_vec(), vec![3, 1, 2]);
+    /// ```
+    fn cycle_newest_to_front(&mut self) {
+        self.rotate(-1);
+    }
+
+    /// Sets every element in the ringbuffer to the value returned by f.
+    fn fill_with<F: FnMut() -> T>(&mut self, f: F);
+
+    /// Sets every element in the ringbuffer to the value returned by f, which is passed the
+    /// index of the element, going from 0 to the capacity of the buffer.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<f32, 4>::new();
+    /// rb.fill_with_indexed(|i| i as f32 * 0.5);
+    /// assert_eq!(rb.to_vec(), vec![0.0, 0.5, 1.0, 1.5]);
+    /// ```
+    fn fill_with_indexed<F: FnMut(usize) -> T>(&mut self, mut f: F) {
+        let mut index = 0;
+        self.fill_with(|| {
+            let value = f(index);
+            index += 1;
+            value
+        });
+    }
+
+    /// Sets every element in the ringbuffer to it's default value
+    fn fill_default(&mut self)
+    where
+        T: Default,
+    {
+        self.fill_with(Default::default);
+    }
+
+    /// Sets every element in the ringbuffer to `value`
+    fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.fill_with(|| value.clone());
+    }
+
+    /// Empties the buffer entirely. Sets the length to 0 but keeps the capacity allocated.
+    fn clear(&mut self);
+
+    /// Gets a value relative to the current index. 0 is the next index to be written to with push.
+    /// -1 and down are the last elements pushed and 0 and up are the items that were pushed the longest ago.
+    fn get_signed(&self, index: isize) -> Option<&T>;
+
+    /// Gets a value relative to the current index. 0 is the next index to be written to with push.
+    fn get(&self, index: usize) -> Option<&T>;
+
+    /// Gets a value like [`get_signed`](RingBuffer::get_signed), but without wrapping around:
+    /// `index` must be in `-len..len`. 0 and up count from the oldest element, -1 and down
+    /// from the newest one.
+    ///
+    /// # Errors
+    /// Returns an [`IndexError`] with the requested index and the length of the buffer when
+    /// `index` is out of range.
+    fn try_get(&self, index: isize) -> Result<&T, IndexError> {
+        let len = self.len();
+        let in_range = if index >= 0 {
+            (index as usize) < len
+        } else {
+            index.unsigned_abs() <= len
+        };
+
+        if in_range {
+            // the index is in range, so get_signed does not wrap and returns an element
+            self.get_signed(index).ok_or(IndexError { index, len })
+        } else {
+            Err(IndexError { index, len })
+        }
+    }
+
+    /// Gets a value by its index counting from the oldest element, clamping `index` to the
+    /// valid range instead of failing: an index past the end returns the newest element, and
+    /// a negative index returns the oldest one. Only returns `None` when the buffer is empty.
+    ///
+    /// This differs from [`get`](RingBuffer::get), which wraps around, and from
+    /// [`get_signed`](RingBuffer::get_signed), where negative indices count from the newest
+    /// element. It's useful for clamp-to-edge boundary handling when running a filter kernel
+    /// over the buffer.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// assert_eq!(rb.get_clamped(-2), Some(&1));
+    /// assert_eq!(rb.get_clamped(1), Some(&2));
+    /// assert_eq!(rb.get_clamped(5), Some(&3));
+    /// ```
+    fn get_clamped(&self, index: isize) -> Option<&T> {
+        let last = self.len().checked_sub(1)?;
+        let index = if index < 0 {
+            0
+        } else {
+            (index as usize).min(last)
+        };
+        self.get(index)
+    }
+
+    /// Gets a value relative to the current index mutably. 0 is the next index to be written to with push.
+    /// -1 and down are the last elements pushed and 0 and up are the items that were pushed the longest ago.
+    #[inline]
+    fn get_mut_signed(&mut self, index: isize) -> Option<&mut T> {
+        // Safety: self is a RingBuffer
+        unsafe { Self::ptr_get_mut_signed(self, index).map(|i| &mut *i) }
+    }
+
+    /// Gets a value relative to the current index mutably. 0 is the next index to be written to with push.
+    #[inline]
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // Safety: self is a RingBuffer
+        unsafe { Self::ptr_get_mut(self, index).map(|i| &mut *i) }
+    }
+
+    /// same as [`get_mut`](RingBuffer::get_mut) but on raw pointers.
+    ///
+    /// # Safety
+    /// ONLY SAFE WHEN self is a *mut to to an implementor of `RingBuffer`
+    #[doc(hidden)]
+    unsafe fn ptr_get_mut(rb: *mut Self, index: usize) -> Option<*mut T>;
+
+    /// same as [`get_mut`](RingBuffer::get_mut) but on raw pointers.
+    ///
+    /// # Safety
+    /// ONLY SAFE WHEN self is a *mut to to an implementor of `RingBuffer`
+    #[doc(hidden)]
+    unsafe fn ptr_get_mut_signed(rb: *mut Self, index: isize) -> Option<*mut T>;
+
+    /// Returns the value at the current index.
+    /// This is the value that will be overwritten by the next push and also the value pushed
+    /// the longest ago. (alias of [`Self::front`])
+    #[inline]
+    fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    /// Returns the value at the front of the queue.
+    /// This is the value that will be overwritten by the next push and also the value pushed
+    /// the longest ago.
+    /// (alias of peek)
+    #[inline]
+    fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns a mutable reference to the value at the back of the queue.
+    /// This is the value that will be overwritten by the next push.
+    /// (alias of peek)
+    #[inline]
+    fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns the value at the back of the queue.
+    /// This is the item that was pushed most recently.
+    #[inline]
+    fn back(&self) -> Option<&T> {
+        self.get_signed(-1)
+    }
+
+    /// Returns a mutable reference to the value at the back of the queue.
+    /// This is the item that was pushed most recently.
+    #[inline]
+    fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut_signed(-1)
+    }
+
+    /// Returns a mutable reference to the most recently pushed element. Only if the buffer is
+    /// empty, the value returned by `f` is pushed first.
+    ///
+    /// Note that this never pushes onto a non-empty buffer, so it is meant for accumulating into
+    /// the newest element, where new elements are started explicitly with [`push`](RingBuffer::push).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// *rb.get_or_push_with(|| 0) += 5;
+    /// *rb.get_or_push_with(|| 0) += 5;
+    /// assert_eq!(rb.to_vec(), vec![10]);
+    ///
+    /// rb.push(0);
+    /// *rb.get_or_push_with(|| 0) += 1;
+    /// assert_eq!(rb.to_vec(), vec![10, 1]);
+    /// ```
+    fn get_or_push_with<F: FnOnce() -> T>(&mut self, f: F) -> &mut T {
+        if self.is_empty() {
+            self.push(f());
+        }
+        self.back_mut()
+            .expect("buffer can't be empty after pushing an element")
+    }
+
+    /// Returns a [`LastEntry`] for the most recently pushed element, which allows updating it,
+    /// replacing it, or pushing a new element after it. Returns `None` when the buffer is empty.
+    #[inline]
+    fn last_entry(&mut self) -> Option<LastEntry<'_, T, Self>> {
+        LastEntry::new(self)
+    }
+
+    /// Creates a mutable iterator over the buffer starting from the item pushed the longest ago,
+    /// and ending at the element most recently pushed.
+    #[inline]
+    fn iter_mut(&mut self) -> RingBufferMutIterator<'_, T, Self> {
+        RingBufferMutIterator::new(self)
+    }
+
+    /// Creates an iterator over the buffer starting from the item pushed the longest ago,
+    /// and ending at the element most recently pushed.
+    #[inline]
+    fn iter(&self) -> RingBufferIterator<'_, T, Self> {
+        RingBufferIterator::new(self)
+    }
+
+    /// Creates an iterator over all pairs of adjacent elements, oldest first. A buffer with
+    /// fewer than two elements yields nothing.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 4, 9, 16]);
+    ///
+    /// let differences: Vec<i32> = rb.pairs().map(|(a, b)| b - a).collect();
+    /// assert_eq!(differences, vec![3, 5, 7]);
+    /// ```
+    fn pairs<'a>(
+        &'a self,
+    ) -> Zip<RingBufferIterator<'a, T, Self>, Skip<RingBufferIterator<'a, T, Self>>>
+    where
+        T: 'a,
+    {
+        self.iter().zip(self.iter().skip(1))
+    }
+
+    /// Creates an iterator over the positions where this buffer and `other` differ, yielding
+    /// the index along with both elements. Indices count from the oldest element, like
+    /// [`get`](RingBuffer::get).
+    ///
+    /// When the buffers have a different length, only the first `min(self.len(), other.len())`
+    /// elements are compared.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let a = ConstGenericRingBuffer::from([1, 2, 3, 4]);
+    /// let b = ConstGenericRingBuffer::from([1, 5, 3, 6]);
+    ///
+    /// let diff: Vec<_> = a.diff(&b).collect();
+    /// assert_eq!(diff, vec![(1, &2, &5), (3, &4, &6)]);
+    /// ```
+    fn diff<'a>(&'a self, other: &'a Self) -> RingBufferDiffIterator<'a, T, Self>
+    where
+        T: PartialEq + 'a,
+    {
+        RingBufferDiffIterator::new(self, other)
+    }
+
+    /// Creates an iterator over the leading elements for which `pred` returns true, oldest
+    /// first, stopping at the first element for which it returns false. Unlike draining, the
+    /// elements stay in the buffer, so this can be used to find out how many elements at the
+    /// front match before removing them.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 5, 3]);
+    /// assert_eq!(rb.peek_while(|x| *x < 4).count(), 2);
+    /// assert_eq!(rb.len(), 4);
+    /// ```
+    #[inline]
+    fn peek_while<'a, F: FnMut(&T) -> bool>(
+        &'a self,
+        pred: F,
+    ) -> RingBufferPeekWhileIterator<'a, T, Self, F>
+    where
+        T: 'a,
+    {
+        RingBufferPeekWhileIterator::new(self.iter(), pred)
+    }
+
+    /// Creates an iterator over the buffer starting from the element most recently pushed,
+    /// and ending at the item pushed the longest ago. The same as `iter().rev()`.
+    #[inline]
+    fn iter_rev<'a>(&'a self) -> Rev<RingBufferIterator<'a, T, Self>>
+    where
+        T: 'a,
+    {
+        self.iter().rev()
+    }
+
+    /// Creates an iterator over the elements paired with their age, newest first. The age is
+    /// the number of elements pushed after it: 0 for the newest element, up to `len() - 1` for
+    /// the oldest one. This is [`iter_rev`](RingBuffer::iter_rev) enumerated, and useful for
+    /// weighting elements by recency.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec!['a', 'b', 'c']);
+    /// let aged: Vec<_> = rb.iter_aged().collect();
+    /// assert_eq!(aged, vec![(0, &'c'), (1, &'b'), (2, &'a')]);
+    /// ```
+    #[inline]
+    fn iter_aged<'a>(&'a self) -> Enumerate<Rev<RingBufferIterator<'a, T, Self>>>
+    where
+        T: 'a,
+    {
+        self.iter_rev().enumerate()
+    }
+
+    /// Creates an iterator over owned clones of the elements in the buffer, in the same order as
+    /// [`iter`](RingBuffer::iter).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.push(String::from("a"));
+    /// rb.push(String::from("b"));
+    ///
+    /// let owned: Vec<String> = rb.cloned().collect();
+    /// assert_eq!(owned, vec!["a", "b"]);
+    /// ```
+    #[inline]
+    fn cloned<'a>(&'a self) -> Cloned<RingBufferIterator<'a, T, Self>>
+    where
+        T: Clone + 'a,
+    {
+        self.iter().cloned()
+    }
+
+    /// Converts the buffer to a vector. This Copies all elements in the ringbuffer.
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Clones the elements into an array, oldest first, if the buffer holds exactly `M`
+    /// elements. Otherwise returns `None`. Unlike [`to_vec`](RingBuffer::to_vec), this does
+    /// not allocate.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let rb: ConstGenericRingBuffer<_, 4> = [1, 2, 3].into_iter().collect();
+    /// assert_eq!(rb.to_array::<3>(), Some([1, 2, 3]));
+    /// assert_eq!(rb.to_array::<4>(), None);
+    /// ```
+    fn to_array<const M: usize>(&self) -> Option<[T; M]>
+    where
+        T: Clone,
+    {
+        if self.len() != M {
+            return None;
+        }
+
+        let mut iter = self.iter();
+        Some([(); M].map(|_| {
+            iter.next()
+                .cloned()
+                .expect("the buffer holds exactly M elements")
+        }))
+    }
+
+    /// Maps the elements to a vector oldest first, like [`Iterator::scan`], passing `f` a
+    /// mutable state which starts as `init` and is carried from one element to the next.
+    /// The vector is allocated with room for [`len`](RingBuffer::len) elements up front.
+    ///
+    /// This is useful for prefix computations over the window, like running sums or applying
+    /// an IIR filter.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 3, 4]);
+    /// let prefix_sums = rb.scan_window(0, |sum, x| {
+    ///     *sum += x;
+    ///     *sum
+    /// });
+    /// assert_eq!(prefix_sums, vec![1, 3, 6, 10]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn scan_window<S, F: FnMut(&mut S, &T) -> T>(&self, init: S, mut f: F) -> Vec<T> {
+        let mut state = init;
+        let mut res = Vec::with_capacity(self.len());
+        res.extend(self.iter().map(|x| f(&mut state, x)));
+        res
+    }
+
+    /// Creates a new [`AllocRingBuffer`] with the elements of `self` and `other` interleaved,
+    /// oldest first: `self[0], other[0], self[1], other[1], ...`. When one buffer is longer,
+    /// its remaining elements are appended at the end. This combines two mono signal windows
+    /// into one stereo stream, for example.
+    ///
+    /// The capacity of the result is the sum of both lengths, or 1 when both are empty, since a
+    /// capacity can't be zero.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let left = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// let right = AllocRingBuffer::from(vec![10, 20]);
+    /// assert_eq!(left.interleave(&right).to_vec(), vec![1, 10, 2, 20, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn interleave(&self, other: &Self) -> AllocRingBuffer<T>
+    where
+        T: Clone,
+    {
+        let mut res = AllocRingBuffer::new((self.len() + other.len()).max(1));
+        let (mut a, mut b) = (self.iter(), other.iter());
+        loop {
+            match (a.next(), b.next()) {
+                (None, None) => break,
+                (x, y) => {
+                    res.extend(x.cloned());
+                    res.extend(y.cloned());
+                }
+            }
+        }
+        res
+    }
+
+    /// Run-length encodes the elements, oldest first: returns each run of consecutive equal
+    /// elements as the value and the length of the run. A run which wraps around the end of the
+    /// underlying storage is still returned as one run.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec!['a', 'a', 'b', 'a', 'a', 'a']);
+    /// assert_eq!(rb.run_length_encode(), vec![('a', 2), ('b', 1), ('a', 3)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn run_length_encode(&self) -> Vec<(T, usize)>
+    where
+        T: PartialEq + Clone,
+    {
+        let (first, second) = self.as_slices();
+        let mut res: Vec<(T, usize)> = Vec::new();
+        for value in first.iter().chain(second) {
+            match res.last_mut() {
+                Some((last, count)) if last == value => *count += 1,
+                _ => res.push((value.clone(), 1)),
+            }
+        }
+        res
+    }
+
+    /// Merges two buffers which are sorted oldest first into a new sorted [`AllocRingBuffer`].
+    /// The merge is stable: of equal elements, those of `self` come first. This combines sorted
+    /// sub-windows without sorting them again.
+    ///
+    /// The capacity of the result is the sum of both lengths, or 1 when both are empty. When
+    /// either input isn't sorted, the result holds all elements in an unspecified order.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let a = AllocRingBuffer::from(vec![1, 4, 6]);
+    /// let b = AllocRingBuffer::from(vec![2, 3, 7]);
+    /// assert_eq!(a.merge_sorted(&b).to_vec(), vec![1, 2, 3, 4, 6, 7]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn merge_sorted(&self, other: &Self) -> AllocRingBuffer<T>
+    where
+        T: Ord + Clone,
+    {
+        let mut res = AllocRingBuffer::new((self.len() + other.len()).max(1));
+        let (mut a, mut b) = (self.iter().peekable(), other.iter().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let next = if y < x { b.next() } else { a.next() };
+            res.extend(next.cloned());
+        }
+        res.extend(a.chain(b).cloned());
+        res
+    }
+
+    /// Creates a new [`AllocRingBuffer`] holding the differences between consecutive elements,
+    /// oldest first: element `i` of the result is `self[i + 1] - self[i]`. This computes velocity
+    /// from a window of positions, for example.
+    ///
+    /// The result is full, with one element less than `self`. When `self` holds fewer than two
+    /// elements, it is empty with a capacity of 1, since a capacity can't be zero.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 4, 9, 16]);
+    /// assert_eq!(rb.diffs().to_vec(), vec![3, 5, 7]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn diffs(&self) -> AllocRingBuffer<T>
+    where
+        T: Copy + Sub<Output = T>,
+    {
+        let mut res = AllocRingBuffer::new(self.len().saturating_sub(1).max(1));
+        res.extend(self.pairs().map(|(&a, &b)| b - a));
+        res
+    }
+
+    /// Clones the elements into a new [`GrowableAllocRingBuffer`], oldest first. It starts out
+    /// with a capacity of [`len`](RingBuffer::len), and grows when more elements are pushed.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2]);
+    /// let mut growable = rb.to_growable();
+    /// growable.push(3);
+    /// assert_eq!(growable.to_vec(), vec![1, 2, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn to_growable(&self) -> GrowableAllocRingBuffer<T>
+    where
+        T: Clone,
+    {
+        let mut res = GrowableAllocRingBuffer::with_capacity(self.len());
+        res.extend(self.iter().cloned());
+        res
+    }
+
+    /// Returns references to the `n` oldest elements, oldest first, or to all elements when
+    /// there are fewer than `n`. The elements stay in the buffer.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// assert_eq!(rb.oldest(2), vec![&1, &2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn oldest(&self, n: usize) -> Vec<&T> {
+        self.iter().take(n).collect()
+    }
+
+    /// Returns references to the `n` newest elements, in the same order as
+    /// [`iter`](RingBuffer::iter), so oldest first, or to all elements when there are fewer
+    /// than `n`. The elements stay in the buffer.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// assert_eq!(rb.newest(2), vec![&2, &3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn newest(&self, n: usize) -> Vec<&T> {
+        self.iter().skip(self.len().saturating_sub(n)).collect()
+    }
+
+    /// Copies the elements into a shared slice, oldest first. The snapshot can be handed to
+    /// several consumers, possibly on other threads, without copying the elements again.
+    #[cfg(feature = "alloc")]
+    fn snapshot_arc(&self) -> Arc<[T]>
+    where
+        T: Clone,
+    {
+        Arc::from(self.to_vec())
+    }
+
+    /// Returns true if the elements of the buffer, oldest first, are equal to the items of
+    /// `iter`: the same values in the same order, and the same number of them. Stops at the
+    /// first difference, without consuming the rest of `iter`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// assert!(rb.eq_iter(1..=3));
+    /// assert!(!rb.eq_iter(1..3));
+    /// ```
+    fn eq_iter<I: IntoIterator<Item = T>>(&self, iter: I) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut iter = iter.into_iter();
+        for elem in self.iter() {
+            match iter.next() {
+                Some(item) if *elem == item => {}
+                _ => return false,
+            }
+        }
+        iter.next().is_none()
+    }
+
+    /// Returns the index of `elem` in the buffer, as used by [`get`](RingBuffer::get), when
+    /// `elem` is a reference to an element in this buffer, for example obtained through
+    /// [`iter`](RingBuffer::iter). Otherwise returns `None`.
+    ///
+    /// Note that this compares the address of `elem`, not its value: an equal value stored
+    /// elsewhere is not found. For zero sized types addresses are meaningless, so this always
+    /// returns `None`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3]);
+    /// let elem = rb.iter().find(|&&x| x == 2).unwrap();
+    /// assert_eq!(rb.index_of(elem), Some(1));
+    /// assert_eq!(rb.index_of(&2), None);
+    /// ```
+    fn index_of(&self, elem: &T) -> Option<usize> {
+        let size = size_of::<T>();
+        if size == 0 {
+            return None;
+        }
+
+        let ptr: *const T = elem;
+        let addr = ptr as usize;
+        let (first, second) = self.as_slices();
+        let offset_in = |slice: &[T]| {
+            let start = slice.as_ptr() as usize;
+            let end = start + size_of_val(slice);
+            (start..end).contains(&addr).then(|| (addr - start) / size)
+        };
+
+        offset_in(first).or_else(|| offset_in(second).map(|offset| first.len() + offset))
+    }
+
+    /// Returns true if elem is in the ringbuffer.
+    fn contains(&self, elem: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|i| i == elem)
+    }
+
+    /// Sums all elements after converting them into `S`.
+    ///
+    /// Summing with `iter().sum()` uses the element type as accumulator, so buffers of small
+    /// integers easily overflow. Picking a wider `S` avoids this:
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<u8, 4>::new();
+    /// rb.fill(200);
+    /// assert_eq!(rb.sum_into::<u32>(), 800);
+    /// ```
+    fn sum_into<S>(&self) -> S
+    where
+        T: Clone + Into<S>,
+        S: Sum<S>,
+    {
+        self.iter().cloned().map(Into::into).sum()
+    }
+
+    /// Multiplies all elements after converting them into `S`.
+    /// See [`sum_into`](RingBuffer::sum_into) for why this is useful.
+    fn product_into<S>(&self) -> S
+    where
+        T: Clone + Into<S>,
+        S: Product<S>,
+    {
+        self.iter().cloned().map(Into::into).product()
+    }
+
+    /// Computes the weighted mean of the newest `weights.len()` elements: the sum of every
+    /// element multiplied by its weight, divided by the sum of the weights. This applies an FIR
+    /// filter to the end of the window.
+    ///
+    /// The weights are aligned with the elements oldest first, so `weights[0]` applies to the
+    /// oldest of the newest `weights.len()` elements, and the last weight to the newest element.
+    ///
+    /// Returns `None` when `weights` is empty or when the buffer holds fewer elements than there
+    /// are weights. Weights which sum to zero give an infinite or NaN result.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![100.0, 1.0, 2.0, 4.0]);
+    /// assert_eq!(rb.weighted_mean(&[1.0, 1.0, 2.0]), Some(11.0 / 4.0));
+    /// ```
+    fn weighted_mean(&self, weights: &[f64]) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        if weights.is_empty() || weights.len() > self.len() {
+            return None;
+        }
+
+        let skip = self.len() - weights.len();
+        let sum: f64 = self
+            .iter()
+            .skip(skip)
+            .zip(weights)
+            .map(|(&x, w)| x.into() * w)
+            .sum();
+        Some(sum / weights.iter().sum::<f64>())
+    }
+
+    /// Computes the autocorrelation of the elements at `lag`: the sum of the products of every
+    /// pair of elements `lag` places apart, divided by the sum of the squares of all elements.
+    /// The normalization makes the autocorrelation at lag 0 equal to 1, and keeps every lag
+    /// between -1 and 1. No mean is subtracted.
+    ///
+    /// For a periodic signal, the autocorrelation peaks at lags which are multiples of the
+    /// period, which makes this useful for pitch detection. Since there are fewer pairs at
+    /// larger lags, those values are biased towards 0.
+    ///
+    /// Returns `None` when `lag` isn't smaller than [`len`](RingBuffer::len), or when every
+    /// element is zero.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1.0, -1.0, 1.0, -1.0]);
+    /// assert_eq!(rb.autocorrelation(0), Some(1.0));
+    /// assert_eq!(rb.autocorrelation(1), Some(-0.75));
+    /// assert_eq!(rb.autocorrelation(4), None);
+    /// ```
+    fn autocorrelation(&self, lag: usize) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        if lag >= self.len() {
+            return None;
+        }
+
+        let energy: f64 = self.iter().map(|&x| x.into() * x.into()).sum();
+        if energy == 0.0 {
+            return None;
+        }
+        let sum: f64 = self
+            .iter()
+            .zip(self.iter().skip(lag))
+            .map(|(&x, &y)| x.into() * y.into())
+            .sum();
+        Some(sum / energy)
+    }
+
+    /// Runs one step of an IIR filter, using the buffer as its delay line. Takes the next input
+    /// sample and returns the next output sample.
+    ///
+    /// The filter is evaluated in direct form II: the buffer holds the intermediate signal `w`,
+    /// newest last, and each step computes
+    ///
+    /// ```text
+    /// w[n] = x[n] - a[0] * w[n - 1] - a[1] * w[n - 2] - ...
+    /// y[n] = b[0] * w[n] + b[1] * w[n - 1] + b[2] * w[n - 2] + ...
+    /// ```
+    ///
+    /// before pushing `w[n]`. So `b` holds the feedforward coefficients `b0, b1, ...` and `a`
+    /// the feedback coefficients `a1, a2, ...`, where the leading `a0` is left out and taken to be
+    /// 1; divide all coefficients by `a0` first when it isn't. Values of `w` from before the
+    /// buffer was filled count as zero.
+    ///
+    /// # Panics
+    /// Panics when the capacity is smaller than the order of the filter, which is the larger of
+    /// `a.len()` and `b.len() - 1`
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// // y[n] = 0.5 * x[n] + 0.5 * y[n - 1]
+    /// let mut state = AllocRingBuffer::new(1);
+    /// let step: Vec<f64> = (0..3).map(|_| state.iir_step(1.0, &[0.5], &[-0.5])).collect();
+    /// assert_eq!(step, vec![0.5, 0.75, 0.875]);
+    /// ```
+    fn iir_step(&mut self, input: T, b: &[T], a: &[T]) -> T
+    where
+        T: Copy + Default + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let order = a.len().max(b.len().saturating_sub(1));
+        assert!(
+            self.capacity() >= order,
+            "Capacity is smaller than the order of the filter"
+        );
+
+        let len = self.len();
+        // w[n - k] for k >= 1, zero when it isn't in the buffer (yet)
+        let delayed = |k: usize| {
+            (k <= len)
+                .then(|| self.get(len - k))
+                .flatten()
+                .copied()
+                .unwrap_or_default()
+        };
+        let w = (1..=a.len()).fold(input, |acc, k| acc - a[k - 1] * delayed(k));
+        let output = b.iter().enumerate().fold(T::default(), |acc, (k, &b)| {
+            acc + b * if k == 0 { w } else { delayed(k) }
+        });
+
+        self.push(w);
+        output
+    }
+
+    /// Computes a polynomial rolling hash over the elements, oldest first.
+    /// See [`RollingHash`](crate::RollingHash) for the exact polynomial and for
+    /// updating the hash incrementally as elements are pushed and dequeued.
+    fn rolling_hash(&self) -> u64
+    where
+        T: Copy + Into<u64>,
+    {
+        let mut hash = crate::RollingHash::new();
+        self.iter().for_each(|&i| hash.push(i));
+        hash.hash()
+    }
+
+    /// Returns the smallest element, the median and the largest element, or `None` when the
+    /// buffer is empty. This clones the elements into a temporary vector to sort them.
+    ///
+    /// For an even number of elements there are two middle elements. Since they can't be
+    /// averaged in general, the lower one is returned as the median.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![7, 1, 4, 9]);
+    /// assert_eq!(rb.quartiles(), Some((1, 4, 9)));
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn quartiles(&self) -> Option<(T, T, T)>
+    where
+        T: Ord + Clone,
+    {
+        let mut sorted = self.to_vec();
+        sorted.sort_unstable();
+
+        let median = sorted.get((sorted.len().checked_sub(1)?) / 2)?.clone();
+        let max = sorted.pop()?;
+        let min = sorted.into_iter().next().unwrap_or_else(|| max.clone());
+        Some((min, median, max))
+    }
+
+    /// Counts the elements in `buckets` bins of equal width, which together span `min..=max`.
+    /// Returns the count of every bin, from the one starting at `min` to the one ending at `max`.
+    ///
+    /// A value exactly on the edge between two bins is counted in the upper one, except for
+    /// `max` which is counted in the last bin. Values below `min` are counted in the first bin
+    /// and values above `max` in the last one, so every element is counted once.
+    ///
+    /// # Panics
+    /// Panics when `buckets` is 0 or when `min` is not smaller than `max`
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![0.5, 1.5, 1.0, 3.0, 9.0]);
+    /// assert_eq!(rb.histogram(0.0, 4.0, 4), vec![1, 2, 0, 2]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn histogram(&self, min: T, max: T, buckets: usize) -> Vec<usize>
+    where
+        T: Copy + Into<f64>,
+    {
+        let (min, max): (f64, f64) = (min.into(), max.into());
+        assert!(buckets > 0, "There must be at least one bucket");
+        assert!(min < max, "min must be smaller than max");
+
+        let mut counts = alloc::vec![0; buckets];
+        let width = (max - min) / buckets as f64;
+        for &value in self.iter() {
+            // the float to int conversion saturates, so values below min end up at 0
+            let bucket = ((value.into() - min) / width) as usize;
+            counts[bucket.min(buckets - 1)] += 1;
+        }
+        counts
+    }
+
+    /// Counts how often every element occurs in the ringbuffer.
+    /// Returns an empty map when the buffer is empty.
+    #[cfg(feature = "alloc")]
+    fn frequencies(&self) -> BTreeMap<T, usize>
+    where
+        T: Ord + Clone,
+    {
+        let mut res = BTreeMap::new();
+        let (first, second) = self.as_slices();
+        for i in first.iter().chain(second) {
+            match res.get_mut(i) {
+                Some(count) => *count += 1,
+                None => {
+                    let _ = res.insert(i.clone(), 1);
+                }
+            }
+        }
+        res
+    }
+
+    /// Counts how often every element occurs in the ringbuffer, like
+    /// [`frequencies`](RingBuffer::frequencies), but into a [`HashMap`] with any hasher.
+    #[cfg(feature = "std")]
+    fn hash_frequencies<S: BuildHasher + Default>(&self) -> HashMap<T, usize, S>
+    where
+        T: Hash + Eq + Clone,
+    {
+        let mut res = HashMap::default();
+        let (first, second) = self.as_slices();
+        for i in first.iter().chain(second) {
+            match res.get_mut(i) {
+                Some(count) => *count += 1,
+                None => {
+                    let _ = res.insert(i.clone(), 1);
+                }
+            }
+        }
+        res
+    }
+
+    /// Returns a pair of slices which contain, in order, the contents of the ringbuffer.
+    /// The first slice starts at the item pushed the longest ago. The second slice is only
+    /// non-empty when the elements wrap around the end of the underlying storage.
+    fn as_slices(&self) -> (&[T], &[T]);
+
+    /// Returns a pair of mutable slices which contain, in order, the contents of the ringbuffer.
+    /// See [`as_slices`](RingBuffer::as_slices).
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]);
+
+    /// Calls `f` on each non-empty slice of [`as_slices`](RingBuffer::as_slices), oldest first.
+    /// So `f` is called zero times for an empty buffer, once when the elements are contiguous,
+    /// and twice when they wrap around.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let mut chunks = Vec::new();
+    /// rb.for_each_contiguous(|chunk| chunks.push(chunk.to_vec()));
+    /// assert_eq!(chunks, vec![vec![2, 3, 4], vec![5]]);
+    /// ```
+    fn for_each_contiguous<F: FnMut(&[T])>(&self, mut f: F) {
+        let (first, second) = self.as_slices();
+        for chunk in [first, second] {
+            if !chunk.is_empty() {
+                f(chunk);
+            }
+        }
+    }
+
+    /// Calls `f` on each non-empty slice of [`as_mut_slices`](RingBuffer::as_mut_slices),
+    /// oldest first. See [`for_each_contiguous`](RingBuffer::for_each_contiguous).
+    fn for_each_contiguous_mut<F: FnMut(&mut [T])>(&mut self, mut f: F) {
+        let (first, second) = self.as_mut_slices();
+        for chunk in [first, second] {
+            if !chunk.is_empty() {
+                f(chunk);
+            }
+        }
+    }
+
+    /// Returns the index of the largest element, or `None` when the buffer is empty. When
+    /// several elements are the largest, the index of the oldest one is returned.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![3, 9, 2, 9]);
+    /// assert_eq!(rb.argmax(), Some(1));
+    /// ```
+    fn argmax(&self) -> Option<usize>
+    where
+        T: Ord,
+    {
+        let (first, second) = self.as_slices();
+        let mut iter = first.iter().chain(second).enumerate();
+        let mut best = iter.next()?;
+        for (i, x) in iter {
+            if x > best.1 {
+                best = (i, x);
+            }
+        }
+        Some(best.0)
+    }
+
+    /// Returns the index of the smallest element, or `None` when the buffer is empty. When
+    /// several elements are the smallest, the index of the oldest one is returned.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![3, 1, 2, 1]);
+    /// assert_eq!(rb.argmin(), Some(1));
+    /// ```
+    fn argmin(&self) -> Option<usize>
+    where
+        T: Ord,
+    {
+        let (first, second) = self.as_slices();
+        let mut iter = first.iter().chain(second).enumerate();
+        let mut best = iter.next()?;
+        for (i, x) in iter {
+            if x < best.1 {
+                best = (i, x);
+            }
+        }
+        Some(best.0)
+    }
+
+    /// Returns the number of elements for which `pred` returns true, for example how many
+    /// samples in the window exceed a threshold. The same as `iter().filter(pred).count()`,
+    /// but walks over the two slices of [`as_slices`](RingBuffer::as_slices) directly.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 8, 3, 9]);
+    /// assert_eq!(rb.count_matching(|x| *x > 5), 2);
+    /// ```
+    fn count_matching<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second).filter(|x| pred(x)).count()
+    }
+
+    /// Copies the elements into `out`, oldest first, and returns how many were copied: the
+    /// smaller of [`len`](RingBuffer::len) and `out.len()`. The buffer is left unchanged, so this
+    /// can take a snapshot of the window into a preallocated scratch buffer on every tick.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1, 2, 3]);
+    /// let mut scratch = [0; 2];
+    /// assert_eq!(rb.copy_into_aligned(&mut scratch), 2);
+    /// assert_eq!(scratch, [1, 2]);
+    /// ```
+    fn copy_into_aligned(&self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let (first, second) = self.as_slices();
+        let first_len = first.len().min(out.len());
+        let second_len = second.len().min(out.len() - first_len);
+
+        out[..first_len].copy_from_slice(&first[..first_len]);
+        out[first_len..first_len + second_len].copy_from_slice(&second[..second_len]);
+        first_len + second_len
+    }
+
+    /// Checks the invariants of the internal read and write pointers, and returns which one
+    /// doesn't hold. They can only be broken through unsafe code, like a wrong call to
+    /// `advance_write`, so this is meant for tests and debugging after manual manipulation,
+    /// to fail early instead of causing undefined behaviour later.
+    ///
+    /// Only the pointer-based buffers, [`AllocRingBuffer`](crate::AllocRingBuffer),
+    /// [`ConstGenericRingBuffer`](crate::ConstGenericRingBuffer) and
+    /// [`SliceRingBuffer`](crate::SliceRingBuffer), have invariants to check.
+    /// [`GrowableAllocRingBuffer`](crate::GrowableAllocRingBuffer) leaves its bookkeeping to
+    /// `VecDeque`, so it always returns `Ok`.
+    ///
+    /// With the `validate` feature, debug builds check this after the methods which move the
+    /// pointers, like `push`, `dequeue` and `clear`, and panic when it fails. Methods which only
+    /// access the elements aren't checked.
+    ///
+    /// # Errors
+    /// Returns an [`InvariantError`] describing the first invariant which is violated.
+    fn check_invariants(&self) -> Result<(), InvariantError> {
+        Ok(())
+    }
+
+    /// Returns true if the elements are stored in one contiguous run, so the second slice
+    /// returned by [`as_slices`](RingBuffer::as_slices) is empty. An empty buffer is
+    /// contiguous.
+    ///
+    /// This can be used to decide whether the storage needs to be rearranged before an
+    /// operation which requires a single slice.
+    #[inline]
+    fn is_contiguous(&self) -> bool {
+        self.as_slices().1.is_empty()
+    }
+
+    /// Rearranges the storage so the elements are stored in one contiguous run, and returns
+    /// them as a single slice, oldest first. The elements stay in the buffer, so call
+    /// [`clear`](RingBuffer::clear) afterwards to discard them once they are processed.
+    ///
+    /// This is for algorithms which need the whole window as one slice. When the elements are
+    /// already contiguous nothing is moved; otherwise this rotates the storage, which takes
+    /// time proportional to its size.
+    ///
+    /// [`AllocRingBuffer`](crate::AllocRingBuffer) and
+    /// [`ConstGenericRingBuffer`](crate::ConstGenericRingBuffer) do this like their
+    /// `align_read_to`, which lowers the read and write pointers, and so their
+    /// `write_generation`. A [`Reader`](crate::Reader) created before then would skip the
+    /// elements pushed next, so replace it with a new one from `reader` afterwards.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend(1..=6);
+    /// assert!(!rb.is_contiguous());
     ///
-    /// assert_eq!(rb.len(), 8);
+    /// assert_eq!(rb.make_contiguous(), &[3, 4, 5, 6]);
+    /// assert!(rb.is_contiguous());
+    /// ```
+    fn make_contiguous(&mut self) -> &mut [T];
+
+    /// Dequeues up to `max` elements, handing them to `f` as contiguous slices.
+    /// `f` is called once for every non-empty physical half of the dequeued elements, so at most twice.
+    /// The elements are dropped after `f` has seen them.
     ///
-    /// for i in rb.drain() {
-    ///     // prints the numbers 0 through 8
-    ///     println!("{}", i);
-    /// }
+    /// Returns the number of elements dequeued.
     ///
-    /// // No elements remain
-    /// assert_eq!(rb.len(), 0);
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
     ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// let mut sum = 0;
+    /// assert_eq!(rb.dequeue_batch(3, |s| sum += s.iter().sum::<i32>()), 3);
+    /// assert_eq!(sum, 6);
+    /// assert_eq!(rb.len(), 1);
     /// ```
-    fn drain(&mut self) -> RingBufferDrainingIterator<T, Self> {
-        RingBufferDrainingIterator::new(self)
-    }
+    fn dequeue_batch<F: FnMut(&[T])>(&mut self, max: usize, mut f: F) -> usize {
+        let (first, second) = self.as_slices();
+        let first = &first[..max.min(first.len())];
+        let second = &second[..(max - first.len()).min(second.len())];
 
-    /// Sets every element in the ringbuffer to the value returned by f.
-    fn fill_with<F: FnMut() -> T>(&mut self, f: F);
+        if !first.is_empty() {
+            f(first);
+        }
+        if !second.is_empty() {
+            f(second);
+        }
 
-    /// Sets every element in the ringbuffer to it's default value
-    fn fill_default(&mut self)
-    where
-        T: Default,
-    {
-        self.fill_with(Default::default);
-    }
+        let consumed = first.len() + second.len();
+        for _ in 0..consumed {
+            self.skip();
+        }
 
-    /// Sets every element in the ringbuffer to `value`
-    fn fill(&mut self, value: T)
-    where
-        T: Clone,
-    {
-        self.fill_with(|| value.clone());
+        consumed
     }
 
-    /// Empties the buffer entirely. Sets the length to 0 but keeps the capacity allocated.
-    fn clear(&mut self);
-
-    /// Gets a value relative to the current index. 0 is the next index to be written to with push.
-    /// -1 and down are the last elements pushed and 0 and up are the items that were pushed the longest ago.
-    fn get_signed(&self, index: isize) -> Option<&T>;
+    /// Dequeues up to `K` elements into an array, oldest first, without allocating. Returns
+    /// the array and the number of elements dequeued. When fewer than `K` elements were in the
+    /// buffer, the remaining entries of the array are `None`.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb: ConstGenericRingBuffer<_, 4> = [1, 2, 3].into_iter().collect();
+    /// assert_eq!(rb.dequeue_array::<2>(), ([Some(1), Some(2)], 2));
+    /// assert_eq!(rb.dequeue_array::<2>(), ([Some(3), None], 1));
+    /// ```
+    fn dequeue_array<const K: usize>(&mut self) -> ([Option<T>; K], usize) {
+        let count = K.min(self.len());
+        ([(); K].map(|_| self.dequeue()), count)
+    }
+}
 
-    /// Gets a value relative to the current index. 0 is the next index to be written to with push.
-    fn get(&self, index: usize) -> Option<&T>;
+mod iter {
+    use crate::RingBuffer;
+    use core::iter::FusedIterator;
+    use core::marker::PhantomData;
+    use core::ptr::NonNull;
 
-    /// Gets a value relative to the current index mutably. 0 is the next index to be written to with push.
-    /// -1 and down are the last elements pushed and 0 and up are the items that were pushed the longest ago.
-    #[inline]
-    fn get_mut_signed(&mut self, index: isize) -> Option<&mut T> {
-        // Safety: self is a RingBuffer
-        unsafe { Self::ptr_get_mut_signed(self, index).map(|i| &mut *i) }
+    /// `RingBufferDiffIterator` holds references to two `RingBuffer`s and iterates over the
+    /// positions where their elements differ. `index` is the current iterator position.
+    pub struct RingBufferDiffIterator<'rb, T, RB: RingBuffer<T>> {
+        a: &'rb RB,
+        b: &'rb RB,
+        len: usize,
+        index: usize,
+        phantom: PhantomData<T>,
     }
 
-    /// Gets a value relative to the current index mutably. 0 is the next index to be written to with push.
-    #[inline]
-    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
-        // Safety: self is a RingBuffer
-        unsafe { Self::ptr_get_mut(self, index).map(|i| &mut *i) }
+    impl<'rb, T, RB: RingBuffer<T>> RingBufferDiffIterator<'rb, T, RB> {
+        #[inline]
+        pub fn new(a: &'rb RB, b: &'rb RB) -> Self {
+            Self {
+                a,
+                b,
+                len: a.len().min(b.len()),
+                index: 0,
+                phantom: PhantomData,
+            }
+        }
     }
 
-    /// same as [`get_mut`](RingBuffer::get_mut) but on raw pointers.
-    ///
-    /// # Safety
-    /// ONLY SAFE WHEN self is a *mut to to an implementor of `RingBuffer`
-    #[doc(hidden)]
-    unsafe fn ptr_get_mut(rb: *mut Self, index: usize) -> Option<*mut T>;
+    impl<'rb, T: PartialEq + 'rb, RB: RingBuffer<T>> Iterator for RingBufferDiffIterator<'rb, T, RB> {
+        type Item = (usize, &'rb T, &'rb T);
 
-    /// same as [`get_mut`](RingBuffer::get_mut) but on raw pointers.
-    ///
-    /// # Safety
-    /// ONLY SAFE WHEN self is a *mut to to an implementor of `RingBuffer`
-    #[doc(hidden)]
-    unsafe fn ptr_get_mut_signed(rb: *mut Self, index: isize) -> Option<*mut T>;
+        fn next(&mut self) -> Option<Self::Item> {
+            while self.index < self.len {
+                let index = self.index;
+                self.index += 1;
 
-    /// Returns the value at the current index.
-    /// This is the value that will be overwritten by the next push and also the value pushed
-    /// the longest ago. (alias of [`Self::front`])
-    #[inline]
-    fn peek(&self) -> Option<&T> {
-        self.front()
-    }
+                let (a, b) = (self.a.get(index)?, self.b.get(index)?);
+                if a != b {
+                    return Some((index, a, b));
+                }
+            }
+            None
+        }
 
-    /// Returns the value at the front of the queue.
-    /// This is the value that will be overwritten by the next push and also the value pushed
-    /// the longest ago.
-    /// (alias of peek)
-    #[inline]
-    fn front(&self) -> Option<&T> {
-        self.get(0)
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (0, Some(self.len - self.index))
+        }
     }
 
-    /// Returns a mutable reference to the value at the back of the queue.
-    /// This is the value that will be overwritten by the next push.
-    /// (alias of peek)
-    #[inline]
-    fn front_mut(&mut self) -> Option<&mut T> {
-        self.get_mut(0)
+    impl<'rb, T: PartialEq + 'rb, RB: RingBuffer<T>> FusedIterator
+        for RingBufferDiffIterator<'rb, T, RB>
+    {
     }
 
-    /// Returns the value at the back of the queue.
-    /// This is the item that was pushed most recently.
-    #[inline]
-    fn back(&self) -> Option<&T> {
-        self.get_signed(-1)
+    /// `RingBufferPeekWhileIterator` iterates over the leading elements of a `RingBuffer` for
+    /// which `pred` returns true, and stops at the first element for which it doesn't.
+    pub struct RingBufferPeekWhileIterator<'rb, T, RB: RingBuffer<T>, F> {
+        iter: RingBufferIterator<'rb, T, RB>,
+        pred: F,
+        done: bool,
     }
 
-    /// Returns a mutable reference to the value at the back of the queue.
-    /// This is the item that was pushed most recently.
-    #[inline]
-    fn back_mut(&mut self) -> Option<&mut T> {
-        self.get_mut_signed(-1)
+    impl<'rb, T, RB: RingBuffer<T>, F> RingBufferPeekWhileIterator<'rb, T, RB, F> {
+        #[inline]
+        pub fn new(iter: RingBufferIterator<'rb, T, RB>, pred: F) -> Self {
+            Self {
+                iter,
+                pred,
+                done: false,
+            }
+        }
     }
 
-    /// Creates a mutable iterator over the buffer starting from the item pushed the longest ago,
-    /// and ending at the element most recently pushed.
-    #[inline]
-    fn iter_mut(&mut self) -> RingBufferMutIterator<T, Self> {
-        RingBufferMutIterator::new(self)
-    }
+    impl<'rb, T: 'rb, RB: RingBuffer<T>, F: FnMut(&T) -> bool> Iterator
+        for RingBufferPeekWhileIterator<'rb, T, RB, F>
+    {
+        type Item = &'rb T;
 
-    /// Creates an iterator over the buffer starting from the item pushed the longest ago,
-    /// and ending at the element most recently pushed.
-    #[inline]
-    fn iter(&self) -> RingBufferIterator<T, Self> {
-        RingBufferIterator::new(self)
-    }
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.done {
+                return None;
+            }
 
-    /// Converts the buffer to a vector. This Copies all elements in the ringbuffer.
-    #[cfg(feature = "alloc")]
-    fn to_vec(&self) -> Vec<T>
-    where
-        T: Clone,
-    {
-        self.iter().cloned().collect()
+            let elem = self.iter.next()?;
+            if (self.pred)(elem) {
+                Some(elem)
+            } else {
+                self.done = true;
+                None
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            if self.done {
+                (0, Some(0))
+            } else {
+                (0, self.iter.size_hint().1)
+            }
+        }
     }
 
-    /// Returns true if elem is in the ringbuffer.
-    fn contains(&self, elem: &T) -> bool
-    where
-        T: PartialEq,
+    impl<'rb, T: 'rb, RB: RingBuffer<T>, F: FnMut(&T) -> bool> FusedIterator
+        for RingBufferPeekWhileIterator<'rb, T, RB, F>
     {
-        self.iter().any(|i| i == elem)
     }
-}
-
-mod iter {
-    use crate::RingBuffer;
-    use core::iter::FusedIterator;
-    use core::marker::PhantomData;
-    use core::ptr::NonNull;
 
     /// `RingBufferIterator` holds a reference to a `RingBuffer` and iterates over it. `index` is the
     /// current iterator position.
@@ -292,6 +1928,20 @@ mod iter {
         fn size_hint(&self) -> (usize, Option<usize>) {
             (self.len, Some(self.len))
         }
+
+        #[inline]
+        fn count(self) -> usize {
+            self.len - self.index
+        }
+
+        #[inline]
+        fn last(self) -> Option<Self::Item> {
+            if self.index < self.len {
+                self.obj.get(self.len - 1)
+            } else {
+                None
+            }
+        }
     }
 
     impl<'rb, T: 'rb, RB: RingBuffer<T>> FusedIterator for RingBufferIterator<'rb, T, RB> {}
@@ -400,6 +2050,46 @@ mod iter {
         }
     }
 
+    /// `RingBufferDrainReuseIterator` holds a mutable reference to a `RingBuffer` and dequeues
+    /// its elements. When dropped, it clears the buffer, dropping the remaining elements and
+    /// resetting its pointers, so the buffer is left empty with its storage intact.
+    pub struct RingBufferDrainReuseIterator<'rb, T, RB: RingBuffer<T>> {
+        obj: &'rb mut RB,
+        phantom: PhantomData<T>,
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>> RingBufferDrainReuseIterator<'rb, T, RB> {
+        #[inline]
+        pub fn new(obj: &'rb mut RB) -> Self {
+            Self {
+                obj,
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>> Iterator for RingBufferDrainReuseIterator<'rb, T, RB> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            self.obj.dequeue()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.obj.len(), Some(self.obj.len()))
+        }
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>> FusedIterator for RingBufferDrainReuseIterator<'rb, T, RB> {}
+
+    impl<'rb, T, RB: RingBuffer<T>> ExactSizeIterator for RingBufferDrainReuseIterator<'rb, T, RB> {}
+
+    impl<'rb, T, RB: RingBuffer<T>> Drop for RingBufferDrainReuseIterator<'rb, T, RB> {
+        fn drop(&mut self) {
+            self.obj.clear();
+        }
+    }
+
     /// `RingBufferIntoIterator` holds a `RingBuffer` and iterates over it.
     pub struct RingBufferIntoIterator<T, RB: RingBuffer<T>> {
         obj: RB,
@@ -430,8 +2120,187 @@ mod iter {
     }
 }
 
+/// Statistics about a call to [`RingBuffer::extend_reporting`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtendReport {
+    /// The number of elements taken from the iterator and pushed.
+    pub consumed: usize,
+    /// The number of elements that were overwritten to make space.
+    pub evicted: usize,
+    /// The length of the buffer afterwards.
+    pub final_len: usize,
+}
+
+/// The error returned by [`RingBuffer::try_get`] when an index is out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexError {
+    /// The index that was requested.
+    pub index: isize,
+    /// The length of the buffer at the time of the request.
+    pub len: usize,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} is out of range for a ringbuffer of length {}",
+            self.index, self.len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexError {}
+
+/// The error returned by [`RingBuffer::check_invariants`], naming the invariant of the read and
+/// write pointers which doesn't hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The read pointer is ahead of the write pointer.
+    ReadAheadOfWrite {
+        /// The position of the read pointer.
+        readptr: usize,
+        /// The position of the write pointer.
+        writeptr: usize,
+    },
+    /// The pointers are further apart than the capacity.
+    LenExceedsCapacity {
+        /// The distance between the pointers.
+        len: usize,
+        /// The capacity of the buffer.
+        capacity: usize,
+    },
+    /// The capacity is larger than the allocated storage.
+    CapacityExceedsBufferSize {
+        /// The capacity of the buffer.
+        capacity: usize,
+        /// The number of elements allocated.
+        buffer_size: usize,
+    },
+    /// A pointer maps to a slot outside of the allocated storage.
+    IndexOutOfBuffer {
+        /// The slot the pointer maps to.
+        index: usize,
+        /// The number of elements allocated.
+        buffer_size: usize,
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ReadAheadOfWrite { readptr, writeptr } => write!(
+                f,
+                "readptr is ahead of writeptr ({} > {})",
+                readptr, writeptr
+            ),
+            Self::LenExceedsCapacity { len, capacity } => write!(
+                f,
+                "ringbuffer holds more elements than its capacity ({} > {})",
+                len, capacity
+            ),
+            Self::CapacityExceedsBufferSize {
+                capacity,
+                buffer_size,
+            } => write!(
+                f,
+                "capacity is larger than the buffer size ({} > {})",
+                capacity, buffer_size
+            ),
+            Self::IndexOutOfBuffer { index, buffer_size } => write!(
+                f,
+                "pointer maps to a slot outside of the buffer ({} >= {})",
+                index, buffer_size
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvariantError {}
+
+/// Reverses the order of the elements at indices `start..end` by swapping them in place.
+fn reverse_range<T, RB: RingBuffer<T>>(rb: &mut RB, mut start: usize, mut end: usize) {
+    let rb: *mut RB = rb;
+    while end - start > 1 {
+        end -= 1;
+        // Safety: start and end are different indices in bounds, so the pointers don't alias
+        unsafe {
+            let a = RB::ptr_get_mut(rb, start).expect("index in bounds");
+            let b = RB::ptr_get_mut(rb, end).expect("index in bounds");
+            ptr::swap(a, b);
+        }
+        start += 1;
+    }
+}
+
+/// Repairs a buffer when the closure passed to [`RingBuffer::map_in_place`] panics, while the
+/// element at `hole` is moved out. The oldest element is moved into the hole, after which the
+/// copy left at the front is dequeued without dropping it.
+struct MapInPlaceGuard<T, RB: RingBuffer<T>> {
+    rb: *mut RB,
+    hole: *mut T,
+}
+
+impl<T, RB: RingBuffer<T>> Drop for MapInPlaceGuard<T, RB> {
+    fn drop(&mut self) {
+        // Safety: the buffer holds at least the hole, so it has a front element, which is
+        // either the hole itself or an initialized element which doesn't overlap with it
+        unsafe {
+            let front = RB::ptr_get_mut(self.rb, 0).expect("buffer holds the hole");
+            if front != self.hole {
+                ptr::copy_nonoverlapping(front, self.hole, 1);
+            }
+            mem::forget((*self.rb).dequeue());
+        }
+    }
+}
+
+/// Drops the elements left in a ringbuffer, for use in the `Drop` impls of the buffers which
+/// manage their own storage. Without destructors there is nothing to drop, so this skips walking
+/// over the elements when `T` doesn't need dropping.
+pub(crate) fn drop_elements<T, RB: RingBuffer<T>>(rb: &mut RB) {
+    if mem::needs_drop::<T>() {
+        rb.drain().for_each(drop);
+    }
+}
+
+/// Compares the contents of two ringbuffers, given as the pairs of slices returned by
+/// [`as_slices`](RingBuffer::as_slices). The halves are compared as sub-slices rather than element
+/// by element, so types like bytes can use the optimized slice comparison.
+pub(crate) fn slices_eq<T: PartialEq>(a: (&[T], &[T]), b: (&[T], &[T])) -> bool {
+    if a.0.len() + a.1.len() != b.0.len() + b.1.len() {
+        return false;
+    }
+
+    let (mut a_cur, mut a_next) = a;
+    let (mut b_cur, mut b_next) = b;
+    loop {
+        if a_cur.is_empty() {
+            if a_next.is_empty() {
+                // the lengths are equal, so b is exhausted as well
+                return true;
+            }
+            a_cur = a_next;
+            a_next = &[];
+        } else if b_cur.is_empty() {
+            b_cur = b_next;
+            b_next = &[];
+        } else {
+            let n = a_cur.len().min(b_cur.len());
+            if a_cur[..n] != b_cur[..n] {
+                return false;
+            }
+            a_cur = &a_cur[n..];
+            b_cur = &b_cur[n..];
+        }
+    }
+}
+
 pub use iter::{
-    RingBufferDrainingIterator, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+    RingBufferDiffIterator, RingBufferDrainReuseIterator, RingBufferDrainingIterator,
+    RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator, RingBufferPeekWhileIterator,
 };
 
 /// Implement various functions on implementors of [`RingBuffer`].
@@ -521,6 +2390,42 @@ macro_rules! impl_ringbuffer_ext {
             })
         }
 
+        fn check_invariants(&self) -> Result<(), $crate::InvariantError> {
+            let (readptr, writeptr) = (self.$readptr, self.$writeptr);
+            let (capacity, buffer_size) = (self.capacity(), self.buffer_size());
+            if readptr > writeptr {
+                Err($crate::InvariantError::ReadAheadOfWrite { readptr, writeptr })
+            } else if writeptr - readptr > capacity {
+                Err($crate::InvariantError::LenExceedsCapacity {
+                    len: writeptr - readptr,
+                    capacity,
+                })
+            } else if capacity > buffer_size {
+                Err($crate::InvariantError::CapacityExceedsBufferSize {
+                    capacity,
+                    buffer_size,
+                })
+            } else if buffer_size != 0 && $mask(buffer_size, readptr) >= buffer_size {
+                Err($crate::InvariantError::IndexOutOfBuffer {
+                    index: $mask(buffer_size, readptr),
+                    buffer_size,
+                })
+            } else if buffer_size != 0 && $mask(buffer_size, writeptr) >= buffer_size {
+                Err($crate::InvariantError::IndexOutOfBuffer {
+                    index: $mask(buffer_size, writeptr),
+                    buffer_size,
+                })
+            } else {
+                Ok(())
+            }
+        }
+
+        #[inline]
+        fn is_contiguous(&self) -> bool {
+            self.is_empty()
+                || $mask(self.buffer_size(), self.$readptr) + self.len() <= self.buffer_size()
+        }
+
         #[inline]
         fn clear(&mut self) {
             for i in self.drain() {
@@ -529,6 +2434,7 @@ macro_rules! impl_ringbuffer_ext {
 
             self.$readptr = 0;
             self.$writeptr = 0;
+            validate_invariants!(self);
         }
     };
 }
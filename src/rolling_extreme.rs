@@ -0,0 +1,170 @@
+use alloc::collections::VecDeque;
+
+/// Keeps track of the maximum and minimum of a sliding window of values.
+///
+/// Internally this stores two monotonic queues, so both [`push`](RollingExtreme::push) and
+/// [`pop_front`](RollingExtreme::pop_front) take amortized constant time, and querying
+/// the extremes is constant time. This is much cheaper than recomputing the maximum over the
+/// whole window after every push.
+///
+/// Like [`RollingHash`](crate::RollingHash), it is meant to be updated alongside a ringbuffer:
+/// call [`pop_front`](RollingExtreme::pop_front) whenever the buffer evicts an element.
+///
+/// # Example
+/// ```
+/// use ringbuffer::{AllocRingBuffer, RingBuffer, RollingExtreme};
+///
+/// let mut buffer = AllocRingBuffer::new(3);
+/// let mut extreme = RollingExtreme::new();
+///
+/// for value in [4, 1, 7, 3, 2, 0] {
+///     if buffer.is_full() {
+///         extreme.pop_front();
+///     }
+///     buffer.push(value);
+///     extreme.push(value);
+/// }
+///
+/// assert_eq!(extreme.max(), Some(&3));
+/// assert_eq!(extreme.min(), Some(&0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RollingExtreme<T> {
+    // candidates for the maximum, decreasing from front to back, paired with their position
+    max: VecDeque<(usize, T)>,
+    // candidates for the minimum, increasing from front to back, paired with their position
+    min: VecDeque<(usize, T)>,
+
+    // position of the oldest value in the window
+    front: usize,
+    // position the next pushed value gets
+    back: usize,
+}
+
+impl<T> RollingExtreme<T> {
+    /// Creates an empty window.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            max: VecDeque::new(),
+            min: VecDeque::new(),
+            front: 0,
+            back: 0,
+        }
+    }
+
+    /// Returns the number of values in the current window.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.back - self.front
+    }
+
+    /// Returns true if the current window is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the largest value in the current window, or `None` if it is empty.
+    /// When several values are the largest, the newest one is returned.
+    #[must_use]
+    pub fn max(&self) -> Option<&T> {
+        self.max.front().map(|(_, value)| value)
+    }
+
+    /// Returns the smallest value in the current window, or `None` if it is empty.
+    /// When several values are the smallest, the newest one is returned.
+    #[must_use]
+    pub fn min(&self) -> Option<&T> {
+        self.min.front().map(|(_, value)| value)
+    }
+
+    /// Appends `value` to the window as the newest value.
+    pub fn push(&mut self, value: T)
+    where
+        T: PartialOrd + Clone,
+    {
+        while matches!(self.max.back(), Some((_, last)) if *last <= value) {
+            let _ = self.max.pop_back();
+        }
+        while matches!(self.min.back(), Some((_, last)) if *last >= value) {
+            let _ = self.min.pop_back();
+        }
+
+        self.max.push_back((self.back, value.clone()));
+        self.min.push_back((self.back, value));
+        self.back += 1;
+    }
+
+    /// Removes the oldest value from the window.
+    ///
+    /// # Panics
+    /// Panics when the window is empty
+    pub fn pop_front(&mut self) {
+        assert!(!self.is_empty(), "Window is empty");
+
+        if matches!(self.max.front(), Some((position, _)) if *position == self.front) {
+            let _ = self.max.pop_front();
+        }
+        if matches!(self.min.front(), Some((position, _)) if *position == self.front) {
+            let _ = self.min.pop_front();
+        }
+        self.front += 1;
+    }
+
+    /// Empties the window.
+    pub fn clear(&mut self) {
+        self.max.clear();
+        self.min.clear();
+        self.front = 0;
+        self.back = 0;
+    }
+}
+
+impl<T> Default for RollingExtreme<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollingExtreme;
+    use crate::{AllocRingBuffer, RingBuffer};
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut rb = AllocRingBuffer::<u32>::new(7);
+        let mut extreme = RollingExtreme::new();
+        assert_eq!(extreme.max(), None);
+
+        let mut state: u32 = 12345;
+        for _ in 0..10_000 {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let value = (state >> 16) % 100;
+
+            if rb.is_full() {
+                extreme.pop_front();
+            }
+            rb.push(value);
+            extreme.push(value);
+
+            assert_eq!(extreme.len(), rb.len());
+            assert_eq!(extreme.max(), rb.iter().max());
+            assert_eq!(extreme.min(), rb.iter().min());
+        }
+
+        while rb.dequeue().is_some() {
+            extreme.pop_front();
+            assert_eq!(extreme.max(), rb.iter().max());
+            assert_eq!(extreme.min(), rb.iter().min());
+        }
+        assert!(extreme.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pop_empty() {
+        RollingExtreme::<i32>::new().pop_front();
+    }
+}
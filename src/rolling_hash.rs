@@ -0,0 +1,136 @@
+/// An incrementally updated polynomial rolling hash over a sliding window of values.
+///
+/// For a window `x[0], x[1], ..., x[n - 1]` (oldest first), the hash is
+/// `x[0] * B^(n-1) + x[1] * B^(n-2) + ... + x[n - 1]`, computed with wrapping arithmetic
+/// (so modulo `2^64`), where `B` is [`RollingHash::BASE`]. This is the same value
+/// [`RingBuffer::rolling_hash`](crate::RingBuffer::rolling_hash) computes over a whole buffer,
+/// so the two can be used interchangeably.
+///
+/// # Example
+/// ```
+/// use ringbuffer::{AllocRingBuffer, RingBuffer, RollingHash};
+///
+/// let mut buffer = AllocRingBuffer::<u8>::new(4);
+/// let mut hash = RollingHash::new();
+///
+/// for byte in b"hello world" {
+///     if buffer.is_full() {
+///         hash.pop_front(buffer[0]);
+///     }
+///     buffer.push(*byte);
+///     hash.push(*byte);
+/// }
+///
+/// assert_eq!(hash.hash(), buffer.rolling_hash());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RollingHash {
+    hash: u64,
+    len: usize,
+}
+
+impl RollingHash {
+    /// The base of the polynomial used for hashing.
+    pub const BASE: u64 = 257;
+
+    /// Creates the hash of an empty window.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { hash: 0, len: 0 }
+    }
+
+    /// Returns the hash of the current window.
+    #[must_use]
+    pub const fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Returns the number of values in the current window.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the current window is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `value` to the window as the newest value.
+    pub fn push(&mut self, value: impl Into<u64>) {
+        self.hash = self
+            .hash
+            .wrapping_mul(Self::BASE)
+            .wrapping_add(value.into());
+        self.len += 1;
+    }
+
+    /// Removes `value` from the window. `value` must be the oldest value in the window,
+    /// otherwise the hash becomes meaningless.
+    ///
+    /// # Panics
+    /// Panics when the window is empty
+    pub fn pop_front(&mut self, value: impl Into<u64>) {
+        assert!(!self.is_empty(), "Window is empty");
+        self.len -= 1;
+        self.hash = self
+            .hash
+            .wrapping_sub(value.into().wrapping_mul(Self::base_pow(self.len)));
+    }
+
+    /// Computes `BASE^exp` with wrapping arithmetic.
+    fn base_pow(mut exp: usize) -> u64 {
+        let mut base = Self::BASE;
+        let mut res: u64 = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res.wrapping_mul(base);
+            }
+            base = base.wrapping_mul(base);
+            exp >>= 1;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RollingHash;
+    use crate::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer};
+
+    #[test]
+    fn test_incremental_matches_recomputed() {
+        let mut rb = AllocRingBuffer::<u8>::new(5);
+        let mut crb = ConstGenericRingBuffer::<u8, 5>::new();
+        let mut hash = RollingHash::new();
+        assert_eq!(hash.hash(), rb.rolling_hash());
+
+        for i in 0..100u8 {
+            let byte = i.wrapping_mul(37) ^ 0x5a;
+            if rb.is_full() {
+                hash.pop_front(rb[0]);
+            }
+            rb.push(byte);
+            crb.push(byte);
+            hash.push(byte);
+
+            assert_eq!(hash.len(), rb.len());
+            assert_eq!(hash.hash(), rb.rolling_hash());
+            assert_eq!(hash.hash(), crb.rolling_hash());
+        }
+
+        while let Some(byte) = rb.dequeue() {
+            hash.pop_front(byte);
+            assert_eq!(hash.hash(), rb.rolling_hash());
+        }
+        assert!(hash.is_empty());
+        assert_eq!(hash.hash(), 0);
+    }
+
+    #[test]
+    fn test_known_value() {
+        let rb = AllocRingBuffer::from([1u8, 2, 3]);
+        assert_eq!(rb.rolling_hash(), 257 * 257 + 2 * 257 + 3);
+    }
+}
@@ -1,14 +1,20 @@
-use core::ops::{Index, IndexMut};
+use core::ops::{AddAssign, Index, IndexMut};
 
 use crate::ringbuffer_trait::{
-    RingBuffer, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+    drop_elements, slices_eq, RingBuffer, RingBufferIntoIterator, RingBufferIterator,
+    RingBufferMutIterator,
 };
 
 extern crate alloc;
 
 // We need boxes, so depend on alloc
-use crate::{mask_and, GrowableAllocRingBuffer};
-use core::ptr;
+use crate::{
+    mask_and, next_capacity_power_of_two, GrowableAllocRingBuffer, Lagged, Reader, Zeroable,
+};
+use core::mem::size_of;
+use core::mem::MaybeUninit;
+use core::num::NonZeroUsize;
+use core::{ptr, slice};
 
 /// The `AllocRingBuffer` is a `RingBuffer` which is based on a Vec. This means it allocates at runtime
 /// on the heap, and therefore needs the [`alloc`] crate. This struct and therefore the dependency on
@@ -140,8 +146,9 @@ impl From<&str> for AllocRingBuffer<char> {
 }
 
 impl<T, const CAP: usize> From<crate::ConstGenericRingBuffer<T, CAP>> for AllocRingBuffer<T> {
+    /// Moves all elements into a new `AllocRingBuffer` with capacity `CAP`, keeping their order.
     fn from(mut value: crate::ConstGenericRingBuffer<T, CAP>) -> Self {
-        let mut res = AllocRingBuffer::new(value.len());
+        let mut res = AllocRingBuffer::new(CAP);
         res.extend(value.drain());
         res
     }
@@ -149,7 +156,7 @@ impl<T, const CAP: usize> From<crate::ConstGenericRingBuffer<T, CAP>> for AllocR
 
 impl<T> Drop for AllocRingBuffer<T> {
     fn drop(&mut self) {
-        self.drain().for_each(drop);
+        drop_elements(self);
 
         let layout = alloc::alloc::Layout::array::<T>(self.size).unwrap();
         unsafe {
@@ -170,9 +177,7 @@ impl<T: Clone> Clone for AllocRingBuffer<T> {
 
 impl<T: PartialEq> PartialEq for AllocRingBuffer<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.capacity == other.capacity
-            && self.len() == other.len()
-            && self.iter().zip(other.iter()).all(|(a, b)| a == b)
+        self.capacity == other.capacity && slices_eq(self.as_slices(), other.as_slices())
     }
 }
 
@@ -187,6 +192,19 @@ impl<T> IntoIterator for AllocRingBuffer<T> {
     }
 }
 
+/// Iterates over references to the elements, like [`iter`](RingBuffer::iter).
+///
+/// ```
+/// use ringbuffer::{AllocRingBuffer, RingBuffer};
+///
+/// let rb = AllocRingBuffer::from([1, 2, 3]);
+///
+/// let mut sum = 0;
+/// for x in &rb {
+///     sum += x;
+/// }
+/// assert_eq!(sum, 6);
+/// ```
 impl<'a, T> IntoIterator for &'a AllocRingBuffer<T> {
     type Item = &'a T;
     type IntoIter = RingBufferIterator<'a, T, AllocRingBuffer<T>>;
@@ -196,6 +214,18 @@ impl<'a, T> IntoIterator for &'a AllocRingBuffer<T> {
     }
 }
 
+/// Iterates over mutable references to the elements, like [`iter_mut`](RingBuffer::iter_mut).
+///
+/// ```
+/// use ringbuffer::{AllocRingBuffer, RingBuffer};
+///
+/// let mut rb = AllocRingBuffer::from([1, 2, 3]);
+///
+/// for x in &mut rb {
+///     *x += 1;
+/// }
+/// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+/// ```
 impl<'a, T> IntoIterator for &'a mut AllocRingBuffer<T> {
     type Item = &'a mut T;
     type IntoIter = RingBufferMutIterator<'a, T, AllocRingBuffer<T>>;
@@ -215,6 +245,53 @@ impl<T> Extend<T> for AllocRingBuffer<T> {
     }
 }
 
+impl<T> AddAssign<T> for AllocRingBuffer<T> {
+    /// Pushes `rhs` onto the buffer. See [`push`](RingBuffer::push).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb += 1;
+    /// rb += 2;
+    /// rb += 3;
+    /// assert_eq!(rb.to_vec(), vec![2, 3]);
+    /// ```
+    fn add_assign(&mut self, rhs: T) {
+        self.push(rhs);
+    }
+}
+
+impl<T: Clone> AddAssign<&[T]> for AllocRingBuffer<T> {
+    /// Pushes clones of all elements of `rhs` onto the buffer. See [`extend`](Extend::extend).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::<i32>::new(3);
+    /// rb += &[1, 2, 3, 4][..];
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    fn add_assign(&mut self, rhs: &[T]) {
+        self.extend(rhs.iter().cloned());
+    }
+}
+
+impl<T> AddAssign<alloc::vec::Vec<T>> for AllocRingBuffer<T> {
+    /// Pushes all elements of `rhs` onto the buffer. See [`extend`](Extend::extend).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::<i32>::new(3);
+    /// rb += vec![1, 2, 3, 4];
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    fn add_assign(&mut self, rhs: alloc::vec::Vec<T>) {
+        self.extend(rhs);
+    }
+}
+
 unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
     #[inline]
     unsafe fn ptr_capacity(rb: *const Self) -> usize {
@@ -254,6 +331,7 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
         }
 
         self.writeptr += 1;
+        validate_invariants!(self);
     }
 
     fn dequeue(&mut self) -> Option<T> {
@@ -264,6 +342,7 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
             let index = mask_and(self.size, self.readptr);
             let res = unsafe { get_unchecked_mut(self, index) };
             self.readptr += 1;
+            validate_invariants!(self);
 
             // Safety: the fact that we got this maybeuninit from the buffer (with mask) means that
             // it's initialized. If it wasn't the is_empty call would have caught it. Values
@@ -280,16 +359,57 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
         mask_and
     );
 
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let len = self.len();
+        let start = mask_and(self.size, self.readptr);
+        let first_len = len.min(self.size - start);
+
+        // Safety: the first slice runs from readptr up to at most the end of the allocation,
+        // the second one continues at the start of the allocation. Both only cover
+        // initialized elements.
+        unsafe {
+            (
+                slice::from_raw_parts(self.buf.add(start), first_len),
+                slice::from_raw_parts(self.buf, len - first_len),
+            )
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let len = self.len();
+        let start = mask_and(self.size, self.readptr);
+        let first_len = len.min(self.size - start);
+
+        // Safety: see as_slices. The two slices never overlap since len <= size.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(self.buf.add(start), first_len),
+                slice::from_raw_parts_mut(self.buf, len - first_len),
+            )
+        }
+    }
+
+    fn make_contiguous(&mut self) -> &mut [T] {
+        if !self.is_contiguous() {
+            self.align_read_to(self.size);
+        }
+        self.as_mut_slices().0
+    }
+
     #[inline]
     fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
         self.clear();
 
         self.readptr = 0;
-        self.writeptr = self.capacity;
+        self.writeptr = 0;
 
-        for i in 0..self.capacity {
-            unsafe { ptr::write(get_unchecked_mut(self, i), f()) };
+        // writeptr is only advanced after each write, so if f panics only the elements
+        // written so far get dropped
+        while self.writeptr < self.capacity {
+            unsafe { ptr::write(get_unchecked_mut(self, self.writeptr), f()) };
+            self.writeptr += 1;
         }
+        validate_invariants!(self);
     }
 }
 
@@ -318,7 +438,7 @@ impl<T> AllocRingBuffer<T> {
     #[must_use]
     pub fn new(capacity: usize) -> Self {
         assert_ne!(capacity, 0, "Capacity must be greater than 0");
-        let size = capacity.next_power_of_two();
+        let size = next_capacity_power_of_two(capacity);
         let layout = alloc::alloc::Layout::array::<T>(size).unwrap();
         let buf = unsafe { alloc::alloc::alloc(layout) as *mut T };
         Self {
@@ -329,6 +449,438 @@ impl<T> AllocRingBuffer<T> {
             writeptr: 0,
         }
     }
+
+    /// Creates a `AllocRingBuffer` with a certain capacity. Unlike [`new`](AllocRingBuffer::new),
+    /// this can't panic since the capacity is guaranteed to be nonzero by its type.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_nonzero(capacity: NonZeroUsize) -> Self {
+        Self::new(capacity.get())
+    }
+
+    /// Creates a full `AllocRingBuffer` with a capacity of `first.len() + second.len()`, whose
+    /// [`as_slices`](RingBuffer::as_slices) returns slices equal to `first` and `second`. This
+    /// restores the physical layout of a buffer, for example after serializing its slices.
+    ///
+    /// `as_slices` only returns an empty first slice for an empty buffer, so when `first` is
+    /// empty, the elements of `second` end up in the first slice.
+    ///
+    /// Keeping the wrap position means the read pointer can't start at zero, so
+    /// [`write_generation`](Self::write_generation) of the new buffer starts at
+    /// `buffer_size() - first.len() + len()` when `second` isn't empty, rather than at `len()`.
+    ///
+    /// # Panics
+    /// Panics when both slices are empty
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from_slices(&[1, 2], &[3]);
+    /// assert_eq!(rb.as_slices(), (&[1, 2][..], &[3][..]));
+    /// assert_eq!(rb.capacity(), 3);
+    /// ```
+    #[must_use]
+    pub fn from_slices(first: &[T], second: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        let mut rb = Self::new(first.len() + second.len());
+        if !second.is_empty() {
+            // place the first slice at the end of the allocation, so the second one wraps
+            // around to its start
+            rb.readptr = rb.size - first.len();
+            rb.writeptr = rb.readptr;
+        }
+
+        for value in first.iter().chain(second) {
+            rb.push(value.clone());
+        }
+        rb
+    }
+
+    /// Creates a full `AllocRingBuffer` with a certain capacity, in which every element is
+    /// `T::default()`. This is useful for delay lines, which should start out filled with silence.
+    ///
+    /// # Panics
+    /// Panics when capacity is zero
+    #[inline]
+    #[must_use]
+    pub fn full_of_default(capacity: usize) -> Self
+    where
+        T: Default,
+    {
+        let mut rb = Self::new(capacity);
+        rb.fill_default();
+        rb
+    }
+
+    /// Creates a full `AllocRingBuffer` with a certain capacity, in which every element is zero.
+    ///
+    /// This is faster than creating an empty buffer and filling it with
+    /// [`fill_default`](RingBuffer::fill_default), since the memory is requested from the allocator
+    /// already zeroed. It requires the element type to implement [`Zeroable`], meaning a value with
+    /// all bits set to zero is a valid `T`.
+    ///
+    /// # Panics
+    /// Panics when capacity is zero, or when `T` is zero-sized, like `[u8; 0]`, since the
+    /// allocator can't be asked for zero bytes
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_zeroed(capacity: usize) -> Self
+    where
+        T: Zeroable,
+    {
+        assert_ne!(capacity, 0, "Capacity must be greater than 0");
+        assert_ne!(
+            size_of::<T>(),
+            0,
+            "Can't allocate zeroed storage for a zero-sized type"
+        );
+        let size = next_capacity_power_of_two(capacity);
+        let layout = alloc::alloc::Layout::array::<T>(size).unwrap();
+        // Safety: T is Zeroable, so the zeroed memory holds valid elements
+        let buf = unsafe { alloc::alloc::alloc_zeroed(layout) as *mut T };
+        Self {
+            buf,
+            size,
+            capacity,
+            readptr: 0,
+            writeptr: capacity,
+        }
+    }
+
+    /// Rotates the backing storage so the oldest element (the one returned by
+    /// [`peek`](RingBuffer::peek)) is stored at a physical index which is a multiple of `alignment`.
+    ///
+    /// Note that `alignment` is counted in elements, not in bytes. The order of the elements
+    /// is unchanged, only the location in memory is. This takes time proportional to the
+    /// allocated size of the buffer.
+    ///
+    /// Moving the elements lowers the read and write pointers. A [`Reader`] created before
+    /// then would skip the elements pushed next, so replace it with a new one from
+    /// [`reader`](AllocRingBuffer::reader).
+    ///
+    /// # Panics
+    /// Panics when `alignment` is zero
+    pub fn align_read_to(&mut self, alignment: usize) {
+        assert_ne!(alignment, 0, "Alignment must be greater than 0");
+
+        let physical = mask_and(self.size, self.readptr);
+        let shift = physical % alignment;
+        if shift == 0 {
+            return;
+        }
+
+        // Safety: the allocation holds `size` elements. Rotating them as `MaybeUninit` only
+        // moves bytes around, and we adjust the read and write pointers accordingly.
+        let storage =
+            unsafe { slice::from_raw_parts_mut(self.buf.cast::<MaybeUninit<T>>(), self.size) };
+        storage.rotate_left(shift);
+
+        self.readptr -= shift;
+        self.writeptr -= shift;
+        validate_invariants!(self);
+    }
+
+    /// Empties the buffer in constant time, by resetting the read and write pointers.
+    ///
+    /// Unlike [`clear`](RingBuffer::clear), this doesn't visit the elements to drop them, which
+    /// is why it is only available for `Copy` types.
+    ///
+    /// Like `clear`, this resets [`write_generation`](Self::write_generation), so a [`Reader`]
+    /// created before would stop seeing new elements. Replace it with a new one from
+    /// [`reader`](Self::reader).
+    #[inline]
+    pub fn reset(&mut self)
+    where
+        T: Copy,
+    {
+        self.readptr = 0;
+        self.writeptr = 0;
+        validate_invariants!(self);
+    }
+
+    /// Returns the elements in the buffer as two slices, oldest first. This is the same as
+    /// [`as_slices`](RingBuffer::as_slices), named to pair with
+    /// [`write_regions`](AllocRingBuffer::write_regions).
+    #[inline]
+    #[must_use]
+    pub fn read_regions(&self) -> (&[T], &[T]) {
+        self.as_slices()
+    }
+
+    /// Returns the unused capacity of the buffer as two slices of uninitialized elements, in the
+    /// order in which they will be written. Together they are `capacity() - len()` long.
+    ///
+    /// This allows filling the buffer without going through [`push`](RingBuffer::push), for
+    /// example by a DMA transfer into both regions. Afterwards, call
+    /// [`advance_write`](AllocRingBuffer::advance_write) to add the written elements to the
+    /// buffer. Likewise, [`read_regions`](AllocRingBuffer::read_regions) and
+    /// [`advance_read`](AllocRingBuffer::advance_read) consume elements in bulk.
+    pub fn write_regions(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let start = mask_and(self.size, self.writeptr);
+        let (first_len, second_len) = self.spare_regions();
+        let buf = self.buf.cast::<MaybeUninit<T>>();
+
+        // Safety: the spare region directly follows the live elements and is at most
+        // capacity - len long, so it never overlaps them. The first slice runs up to at most
+        // the end of the allocation, the second one continues at its start.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(buf.add(start), first_len),
+                slice::from_raw_parts_mut(buf, second_len),
+            )
+        }
+    }
+
+    /// Returns the lengths of the two regions returned by
+    /// [`write_regions`](AllocRingBuffer::write_regions), without borrowing them. This tells
+    /// how a write of a given size is split across the end of the storage.
+    #[must_use]
+    pub fn spare_regions(&self) -> (usize, usize) {
+        let spare = self.capacity - self.len();
+        let start = mask_and(self.size, self.writeptr);
+        let first_len = spare.min(self.size - start);
+        (first_len, spare - first_len)
+    }
+
+    /// Adds the first `n` elements of the [`write_regions`](AllocRingBuffer::write_regions) to
+    /// the buffer, as the newest elements.
+    ///
+    /// # Safety
+    /// The first `n` elements of the write regions must have been initialized.
+    ///
+    /// # Panics
+    /// Panics when `n` is larger than the unused capacity of the buffer
+    pub unsafe fn advance_write(&mut self, n: usize) {
+        assert!(
+            n <= self.capacity - self.len(),
+            "Can't advance past the unused capacity"
+        );
+        self.writeptr += n;
+        validate_invariants!(self);
+    }
+
+    /// Hands the first of the [`write_regions`](AllocRingBuffer::write_regions) to `f`, which writes
+    /// elements to the start of it and returns how many it wrote. These are then added to the
+    /// buffer as the newest elements, like [`advance_write`](AllocRingBuffer::advance_write) does.
+    ///
+    /// This covers the common case of a single write into the unused capacity. The region ends
+    /// where the storage ends, so it may be shorter than the unused capacity.
+    ///
+    /// # Safety
+    /// `f` must initialize the first `n` elements of the region, where `n` is the value it
+    /// returns.
+    ///
+    /// # Panics
+    /// Panics when `f` returns more than the length of the region
+    pub unsafe fn with_write_region<F: FnOnce(&mut [MaybeUninit<T>]) -> usize>(&mut self, f: F) {
+        let region = self.write_regions().0;
+        let len = region.len();
+        let n = f(region);
+        assert!(n <= len, "Can't write past the end of the region");
+        self.advance_write(n);
+    }
+
+    /// Removes the `n` oldest elements from the buffer, after they were read through the
+    /// [`read_regions`](AllocRingBuffer::read_regions).
+    ///
+    /// # Panics
+    /// Panics when `n` is larger than the length of the buffer
+    pub fn advance_read(&mut self, n: usize) {
+        assert!(n <= self.len(), "Can't advance past the end of the buffer");
+        for _ in 0..n {
+            self.skip();
+        }
+        validate_invariants!(self);
+    }
+}
+
+impl AllocRingBuffer<u8> {
+    /// Returns the XOR of all bytes in the buffer, or 0 when it is empty. This is the
+    /// longitudinal parity of the window.
+    ///
+    /// ```
+    /// use ringbuffer::AllocRingBuffer;
+    ///
+    /// let rb = AllocRingBuffer::from(vec![0b0011u8, 0b0101, 0b1000]);
+    /// assert_eq!(rb.xor_all(), 0b1110);
+    /// ```
+    #[must_use]
+    pub fn xor_all(&self) -> u8 {
+        self.fold_bytes(0, |acc, byte| acc ^ byte)
+    }
+
+    /// Folds all bytes in the buffer into a single byte, oldest first, starting from `init`.
+    ///
+    /// This walks over the two contiguous halves of the buffer in tight loops, so simple
+    /// reductions such as XOR or a wrapping sum can be vectorized by the compiler.
+    ///
+    /// ```
+    /// use ringbuffer::AllocRingBuffer;
+    ///
+    /// let rb = AllocRingBuffer::from(vec![200u8, 100, 3]);
+    /// assert_eq!(rb.fold_bytes(0, u8::wrapping_add), 47);
+    /// ```
+    pub fn fold_bytes<F: FnMut(u8, u8) -> u8>(&self, init: u8, mut f: F) -> u8 {
+        let (first, second) = self.as_slices();
+        let acc = first.iter().fold(init, |acc, &byte| f(acc, byte));
+        second.iter().fold(acc, |acc, &byte| f(acc, byte))
+    }
+
+    /// Returns an iterator over the bytes as consecutive records of `R` bytes, oldest first.
+    /// The records are copied out, so the bytes stay in the buffer. When the length isn't a
+    /// multiple of `R`, the incomplete record at the end is left out.
+    ///
+    /// # Panics
+    /// Panics when `R` is zero
+    ///
+    /// ```
+    /// use ringbuffer::AllocRingBuffer;
+    ///
+    /// let rb = AllocRingBuffer::from(vec![1u8, 2, 3, 4, 5]);
+    /// let records: Vec<[u8; 2]> = rb.records().collect();
+    /// assert_eq!(records, vec![[1, 2], [3, 4]]);
+    /// ```
+    pub fn records<const R: usize>(&self) -> impl Iterator<Item = [u8; R]> + '_ {
+        assert_ne!(R, 0, "Record size must be greater than 0");
+
+        let (first, second) = self.as_slices();
+        (0..self.len() / R).map(move |i| {
+            let start = i * R;
+            let mut record = [0; R];
+            if start + R <= first.len() {
+                record.copy_from_slice(&first[start..start + R]);
+            } else if start >= first.len() {
+                let start = start - first.len();
+                record.copy_from_slice(&second[start..start + R]);
+            } else {
+                // the record wraps around the end of the storage
+                let bytes = first[start..].iter().chain(second);
+                record.iter_mut().zip(bytes).for_each(|(r, &b)| *r = b);
+            }
+            record
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl AllocRingBuffer<u8> {
+    /// Reads at most `max` bytes from `reader` directly into the unused capacity of the buffer,
+    /// without an intermediate buffer. Returns the number of bytes read, which were added as the
+    /// newest elements.
+    ///
+    /// Like [`Read::read`](std::io::Read::read), this doesn't wait for `max` bytes to become
+    /// available. When the unused capacity wraps around, a second read fills the part at the
+    /// start of the storage if the first one filled the part at the end. Nothing is overwritten:
+    /// when the buffer is full, this reads nothing and returns 0.
+    ///
+    /// # Errors
+    /// Returns the error of `reader`, unless some bytes were read already. Reads which are
+    /// interrupted are retried. A reader which claims to have read more bytes than it was given
+    /// room for is treated as failing with [`InvalidData`](std::io::ErrorKind::InvalidData).
+    pub fn fill_from_reader<R: std::io::Read>(
+        &mut self,
+        reader: &mut R,
+        max: usize,
+    ) -> std::io::Result<usize> {
+        let n = crate::byte_io::read_into_regions(reader, self.write_regions(), max)?;
+        // Safety: the first n bytes of the write regions were initialized by the read
+        unsafe { self.advance_write(n) };
+        Ok(n)
+    }
+
+    /// Writes at most `max` of the oldest bytes to `writer`, and removes the bytes which were
+    /// written from the buffer. Returns the number of bytes written.
+    ///
+    /// Like [`Write::write`](std::io::Write::write), this doesn't guarantee that `max` bytes are
+    /// written. When the bytes wrap around, a second write sends the part at the start of the
+    /// storage if the first one was written completely. After a short write, only the bytes
+    /// that were accepted by `writer` are removed.
+    ///
+    /// # Errors
+    /// Returns the error of `writer`, unless some bytes were written already. Writes which are
+    /// interrupted are retried. A writer which claims to have written more bytes than it was
+    /// given is treated as failing with [`InvalidData`](std::io::ErrorKind::InvalidData).
+    pub fn flush_to_writer<W: std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        max: usize,
+    ) -> std::io::Result<usize> {
+        let n = crate::byte_io::write_from_regions(writer, self.read_regions(), max)?;
+        self.advance_read(n);
+        Ok(n)
+    }
+}
+
+impl<T> AllocRingBuffer<T> {
+    /// Returns the number of elements pushed since the buffer was created or last cleared,
+    /// including elements that were overwritten or dequeued since.
+    ///
+    /// Comparing this with an earlier value tells whether the buffer has advanced since. An
+    /// element that was the `n`th one ever pushed is still in the buffer when
+    /// `write_generation() - n < len()`.
+    ///
+    /// The counter is reset by [`clear`](RingBuffer::clear) and by methods built on it, like
+    /// [`fill_with`](RingBuffer::fill_with), as well as by [`reset`](Self::reset), and lowered
+    /// by `align_read_to`. A buffer created by [`from_slices`](Self::from_slices) starts counting
+    /// above `len()` to keep the wrap position of its elements, so there only the difference
+    /// between two generations counts pushes. It is never renormalized, so after `usize::MAX`
+    /// pushes it overflows like the internal write index.
+    #[inline]
+    #[must_use]
+    pub fn write_generation(&self) -> usize {
+        self.writeptr
+    }
+
+    /// Creates a [`Reader`] which observes all elements pushed from now on,
+    /// without removing them from the buffer.
+    #[inline]
+    #[must_use]
+    pub fn reader(&self) -> Reader<Self> {
+        Reader::new(self.writeptr)
+    }
+}
+
+impl<T> Reader<AllocRingBuffer<T>> {
+    /// Returns the next element this reader has not seen yet, or `None` when it is up to date.
+    ///
+    /// When the reader lagged behind, it skips to the oldest element still in the buffer.
+    /// Use [`lagged_by`](Reader::lagged_by) to find out how many elements were missed.
+    pub fn next<'a>(&mut self, rb: &'a AllocRingBuffer<T>) -> Option<&'a T> {
+        self.position = self.position.max(rb.readptr);
+        if self.position >= rb.writeptr {
+            return None;
+        }
+
+        // Safety: readptr <= position < writeptr, so this element is initialized
+        let res = unsafe { get_unchecked(rb, mask_and(rb.size, self.position)) };
+        self.position += 1;
+        Some(res)
+    }
+
+    /// Returns the number of elements that were overwritten before this reader saw them.
+    #[must_use]
+    pub fn lagged_by(&self, rb: &AllocRingBuffer<T>) -> usize {
+        rb.readptr.saturating_sub(self.position)
+    }
+
+    /// Returns the next element this reader has not seen yet, or `None` when it is up to date,
+    /// like [`next`](Reader::next). Unlike `next`, this doesn't silently skip elements which
+    /// were overwritten.
+    ///
+    /// # Errors
+    /// Returns [`Lagged`] with the number of missed elements when the reader lagged behind.
+    /// The reader then moves on to the oldest element still in the buffer, which the next call
+    /// returns.
+    pub fn read<'a>(&mut self, rb: &'a AllocRingBuffer<T>) -> Result<Option<&'a T>, Lagged> {
+        let missed = self.lagged_by(rb);
+        if missed > 0 {
+            self.position = rb.readptr;
+            return Err(Lagged { missed });
+        }
+        Ok(self.next(rb))
+    }
 }
 
 /// Get a reference from the buffer without checking it is initialized.
@@ -370,7 +922,8 @@ impl<T> IndexMut<usize> for AllocRingBuffer<T> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{AllocRingBuffer, RingBuffer};
+    use crate::{mask_and, AllocRingBuffer, InvariantError, RingBuffer};
+    use core::num::NonZeroUsize;
 
     // just test that this compiles
     #[test]
@@ -397,10 +950,10 @@ mod tests {
             assert!(rb.is_full());
 
             for i in 0..10 {
-                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue())
+                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue());
             }
 
-            assert!(rb.is_empty())
+            assert!(rb.is_empty());
         }
     }
 
@@ -451,6 +1004,476 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_align_read_to() {
+        let mut rb = AllocRingBuffer::new(8);
+        for i in 0..11 {
+            rb.push(i);
+        }
+        rb.skip();
+        assert_eq!(mask_and(rb.size, rb.readptr), 4);
+
+        rb.align_read_to(3);
+        assert_eq!(mask_and(rb.size, rb.readptr) % 3, 0);
+        assert_eq!(rb.to_vec(), alloc::vec![4, 5, 6, 7, 8, 9, 10]);
+
+        rb.align_read_to(8);
+        assert_eq!(mask_and(rb.size, rb.readptr), 0);
+        assert_eq!(rb.to_vec(), alloc::vec![4, 5, 6, 7, 8, 9, 10]);
+
+        rb.push(11);
+        rb.push(12);
+        assert_eq!(rb.to_vec(), alloc::vec![5, 6, 7, 8, 9, 10, 11, 12]);
+    }
+
+    #[test]
+    #[cfg(feature = "validate")]
+    #[should_panic(expected = "ringbuffer holds more elements than its capacity")]
+    fn test_validate_invariants() {
+        // the buffer is corrupted, so dropping it would read uninitialized elements
+        let mut rb = core::mem::ManuallyDrop::new(AllocRingBuffer::<i32>::new(4));
+        rb.writeptr = 100;
+        rb.push(1);
+    }
+
+    #[test]
+    #[cfg(feature = "validate")]
+    #[should_panic(expected = "capacity is larger than the buffer size")]
+    fn test_validate_invariants_clear() {
+        let mut rb = core::mem::ManuallyDrop::new(AllocRingBuffer::<i32>::new(4));
+        rb.capacity = 5;
+        rb.clear();
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let mut rb = AllocRingBuffer::<i32>::new(3);
+        rb.extend(0..5);
+        assert_eq!(rb.check_invariants(), Ok(()));
+        let (readptr, writeptr) = (rb.readptr, rb.writeptr);
+
+        rb.readptr = writeptr + 1;
+        assert_eq!(
+            rb.check_invariants(),
+            Err(InvariantError::ReadAheadOfWrite {
+                readptr: 6,
+                writeptr: 5
+            })
+        );
+
+        rb.readptr = 0;
+        assert_eq!(
+            rb.check_invariants(),
+            Err(InvariantError::LenExceedsCapacity {
+                len: 5,
+                capacity: 3
+            })
+        );
+
+        rb.readptr = readptr;
+        rb.capacity = 5;
+        assert_eq!(
+            rb.check_invariants(),
+            Err(InvariantError::CapacityExceedsBufferSize {
+                capacity: 5,
+                buffer_size: 4
+            })
+        );
+
+        rb.capacity = 3;
+        assert_eq!(rb.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn test_drain_reuse() {
+        use alloc::rc::Rc;
+
+        let value = Rc::new(());
+        let mut rb = AllocRingBuffer::new(3);
+        rb.extend(core::iter::repeat(value.clone()).take(5));
+        let buf = rb.buf;
+
+        let mut drain = rb.drain_reuse();
+        assert_eq!(drain.len(), 3);
+        drop(drain.next());
+        drop(drain);
+
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert!(rb.is_empty());
+        assert_eq!((rb.readptr, rb.writeptr), (0, 0));
+        assert_eq!(rb.buf, buf);
+        assert_eq!(rb.capacity(), 3);
+
+        rb.push(value.clone());
+        assert_eq!(rb.len(), 1);
+        assert_eq!(rb.as_slices().1.len(), 0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend(0..6);
+        rb.reset();
+        assert!(rb.is_empty());
+        assert_eq!(rb.write_generation(), 0);
+
+        rb.push(10);
+        assert_eq!(rb.to_vec(), alloc::vec![10]);
+    }
+
+    #[test]
+    fn test_regions() {
+        let mut rb = AllocRingBuffer::<u8>::new(6);
+        {
+            let (a, b) = rb.write_regions();
+            assert_eq!((a.len(), b.len()), (6, 0));
+            for (i, slot) in a.iter_mut().take(4).enumerate() {
+                *slot = core::mem::MaybeUninit::new(i as u8);
+            }
+        }
+        unsafe { rb.advance_write(4) };
+        assert_eq!(rb.read_regions(), (&[0, 1, 2, 3][..], &[][..]));
+
+        rb.advance_read(3);
+        assert_eq!(rb.to_vec(), alloc::vec![3]);
+
+        // the spare capacity now wraps around the end of the allocation of 8 elements
+        {
+            let (a, b) = rb.write_regions();
+            assert_eq!((a.len(), b.len()), (4, 1));
+            for (i, slot) in a.iter_mut().chain(b.iter_mut()).enumerate() {
+                *slot = core::mem::MaybeUninit::new(10 + i as u8);
+            }
+        }
+        unsafe { rb.advance_write(5) };
+        assert!(rb.is_full());
+        assert_eq!(rb.to_vec(), alloc::vec![3, 10, 11, 12, 13, 14]);
+        assert_eq!(rb.read_regions(), (&[3, 10, 11, 12, 13][..], &[14][..]));
+
+        let (a, b) = rb.write_regions();
+        assert!(a.is_empty() && b.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fill_from_reader() {
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        rb.extend([0; 6]);
+        rb.advance_read(6);
+
+        // the unused capacity wraps around after two bytes
+        let mut source: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        assert_eq!(rb.fill_from_reader(&mut source, 5).unwrap(), 5);
+        assert_eq!(rb.read_regions(), (&[1, 2][..], &[3, 4, 5][..]));
+        assert_eq!(source, &[6, 7, 8, 9, 10]);
+
+        assert_eq!(rb.fill_from_reader(&mut source, 10).unwrap(), 3);
+        assert!(rb.is_full());
+        assert_eq!(rb.to_vec(), alloc::vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(rb.fill_from_reader(&mut source, 10).unwrap(), 0);
+
+        rb.clear();
+        assert_eq!(rb.fill_from_reader(&mut source, 10).unwrap(), 2);
+        assert_eq!(rb.to_vec(), alloc::vec![9, 10]);
+        assert_eq!(rb.fill_from_reader(&mut source, 10).unwrap(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fill_from_reader_invalid_count() {
+        // claims to have read `n` bytes, and more than it was given room for after that
+        struct Liar(usize);
+        impl std::io::Read for Liar {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Ok(core::mem::replace(&mut self.0, usize::MAX))
+            }
+        }
+
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        rb.extend([0; 6]);
+        rb.advance_read(6);
+        assert_eq!(rb.write_regions().0.len(), 2);
+
+        let error = rb.fill_from_reader(&mut Liar(5), 5).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(rb.is_empty());
+
+        // the bytes of the first region were read correctly, so they are kept
+        assert_eq!(rb.fill_from_reader(&mut Liar(2), 5).unwrap(), 2);
+        assert_eq!(rb.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_flush_to_writer() {
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        rb.extend(0..11);
+        assert_eq!(rb.read_regions(), (&[3, 4, 5, 6, 7][..], &[8, 9, 10][..]));
+
+        let mut sink = alloc::vec::Vec::new();
+        assert_eq!(rb.flush_to_writer(&mut sink, 6).unwrap(), 6);
+        assert_eq!(sink, alloc::vec![3, 4, 5, 6, 7, 8]);
+        assert_eq!(rb.to_vec(), alloc::vec![9, 10]);
+
+        assert_eq!(rb.flush_to_writer(&mut sink, 10).unwrap(), 2);
+        assert!(rb.is_empty());
+        assert_eq!(rb.flush_to_writer(&mut sink, 10).unwrap(), 0);
+        assert_eq!(sink, alloc::vec![3, 4, 5, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_flush_to_writer_short_write() {
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        rb.extend(0..8);
+
+        let mut storage = [0; 3];
+        let mut sink = &mut storage[..];
+        assert_eq!(rb.flush_to_writer(&mut sink, 8).unwrap(), 3);
+        assert_eq!(storage, [0, 1, 2]);
+        assert_eq!(rb.to_vec(), alloc::vec![3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_flush_to_writer_invalid_count() {
+        // claims to have written `n` bytes, and more than it was given after that
+        struct Liar(usize);
+        impl std::io::Write for Liar {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Ok(core::mem::replace(&mut self.0, usize::MAX))
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        rb.extend(0..11);
+        assert_eq!(rb.read_regions().0.len(), 5);
+
+        let error = rb.flush_to_writer(&mut Liar(6), 8).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(rb.len(), 8);
+
+        // the bytes of the first region were written correctly, so only they are removed
+        assert_eq!(rb.flush_to_writer(&mut Liar(5), 8).unwrap(), 5);
+        assert_eq!(rb.to_vec(), alloc::vec![8, 9, 10]);
+    }
+
+    #[test]
+    fn test_fold_bytes() {
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        assert_eq!(rb.xor_all(), 0);
+        assert_eq!(rb.fold_bytes(7, u8::wrapping_add), 7);
+
+        // wrap around the end of the storage
+        rb.extend(0..8);
+        rb.advance_read(5);
+        rb.extend([0x5a, 0xff, 0x13, 0x80]);
+        assert!(!rb.as_slices().1.is_empty());
+
+        let bytes = rb.to_vec();
+        assert_eq!(rb.xor_all(), bytes.iter().fold(0, |acc, b| acc ^ b));
+        let lrc = |acc: u8, byte: u8| acc.wrapping_add(byte);
+        assert_eq!(
+            rb.fold_bytes(0, lrc).wrapping_neg(),
+            bytes.iter().fold(0u8, |acc, &b| lrc(acc, b)).wrapping_neg()
+        );
+    }
+
+    #[test]
+    fn test_records() {
+        let mut rb = AllocRingBuffer::<u8>::new(16);
+        assert_eq!(rb.records::<4>().count(), 0);
+
+        // wrap around the end of the storage, in the middle of the second record
+        rb.extend([0; 10]);
+        rb.advance_read(10);
+        rb.extend(1..=14);
+        assert_eq!(rb.as_slices().0.len(), 6);
+
+        let records: alloc::vec::Vec<[u8; 4]> = rb.records().collect();
+        assert_eq!(
+            records,
+            alloc::vec![[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]]
+        );
+        // the bytes are still there
+        assert_eq!(rb.len(), 14);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_records_zero_size() {
+        let rb = AllocRingBuffer::<u8>::new(4);
+        let _ = rb.records::<0>();
+    }
+
+    #[test]
+    fn test_from_slices() {
+        let rb = AllocRingBuffer::from_slices(&[1, 2, 3], &[4, 5]);
+        assert_eq!(rb.capacity(), 5);
+        assert_eq!(rb.as_slices(), (&[1, 2, 3][..], &[4, 5][..]));
+        assert_eq!(rb.to_vec(), alloc::vec![1, 2, 3, 4, 5]);
+
+        // the counter starts past the three slots left before the first slice
+        assert_eq!(rb.write_generation(), rb.buffer_size() - 3 + 5);
+
+        let copy = AllocRingBuffer::from_slices(rb.as_slices().0, rb.as_slices().1);
+        assert_eq!(copy.as_slices(), rb.as_slices());
+
+        let mut rb = copy;
+        let mut reader = rb.reader();
+        let generation = rb.write_generation();
+        rb.push(6);
+        assert_eq!(rb.write_generation(), generation + 1);
+        assert_eq!(reader.next(&rb), Some(&6));
+
+        let mut rb = AllocRingBuffer::from_slices(&[1, 2], &[]);
+        assert_eq!(rb.as_slices(), (&[1, 2][..], &[][..]));
+        rb.push(3);
+        assert_eq!(rb.to_vec(), alloc::vec![2, 3]);
+
+        let rb = AllocRingBuffer::from_slices(&[], &[1, 2]);
+        assert_eq!(rb.as_slices(), (&[1, 2][..], &[][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_slices_empty() {
+        let _ = AllocRingBuffer::<i32>::from_slices(&[], &[]);
+    }
+
+    #[test]
+    fn test_spare_regions() {
+        let mut rb = AllocRingBuffer::<u8>::new(6);
+        assert_eq!(rb.spare_capacity_len(), 6);
+        assert_eq!(rb.spare_regions(), (6, 0));
+
+        rb.extend(0..4);
+        assert_eq!(rb.spare_capacity_len(), 2);
+        assert_eq!(rb.spare_regions(), (2, 0));
+
+        // the spare capacity wraps around the end of the allocation of 8 elements
+        rb.advance_read(3);
+        rb.extend(4..7);
+        assert_eq!(rb.spare_capacity_len(), 2);
+        assert_eq!(rb.spare_regions(), (1, 1));
+        let (a, b) = rb.write_regions();
+        assert_eq!((a.len(), b.len()), (1, 1));
+
+        rb.extend(7..9);
+        assert!(rb.is_full());
+        assert_eq!(rb.spare_capacity_len(), 0);
+        assert_eq!(rb.spare_regions(), (0, 0));
+
+        rb.clear();
+        assert_eq!(rb.spare_regions(), (6, 0));
+    }
+
+    #[test]
+    fn test_with_write_region() {
+        let mut rb = AllocRingBuffer::<u8>::new(8);
+        rb.extend(0..6);
+        rb.advance_read(6);
+
+        // only the two elements up to the end of the storage are handed out
+        unsafe {
+            rb.with_write_region(|region| {
+                assert_eq!(region.len(), 2);
+                region[0] = core::mem::MaybeUninit::new(1);
+                1
+            });
+        }
+        assert_eq!(rb.len(), 1);
+
+        unsafe {
+            rb.with_write_region(|region| {
+                assert_eq!(region.len(), 1);
+                region[0] = core::mem::MaybeUninit::new(2);
+                1
+            });
+            rb.with_write_region(|region| {
+                assert_eq!(region.len(), 6);
+                region[0] = core::mem::MaybeUninit::new(3);
+                region[1] = core::mem::MaybeUninit::new(4);
+                2
+            });
+        }
+        assert_eq!(rb.to_vec(), alloc::vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_write_region_too_far() {
+        let mut rb = AllocRingBuffer::<u8>::new(2);
+        unsafe { rb.with_write_region(|region| region.len() + 1) };
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_advance_write_too_far() {
+        let mut rb = AllocRingBuffer::<u8>::new(2);
+        rb.push(1);
+        unsafe { rb.advance_write(2) };
+    }
+
+    #[test]
+    fn test_write_generation() {
+        let mut rb = AllocRingBuffer::new(3);
+        assert_eq!(rb.write_generation(), 0);
+
+        for i in 1..=10 {
+            rb.push(i);
+            assert_eq!(rb.write_generation(), i);
+        }
+
+        let _ = rb.dequeue();
+        assert_eq!(rb.write_generation(), 10);
+
+        rb.clear();
+        assert_eq!(rb.write_generation(), 0);
+    }
+
+    #[test]
+    fn test_full_of_default() {
+        let mut rb = AllocRingBuffer::<alloc::string::String>::full_of_default(3);
+        assert!(rb.is_full());
+        assert!(rb.iter().all(|s| s.is_empty()));
+
+        rb.push("a".into());
+        assert_eq!(rb.to_vec(), alloc::vec!["", "", "a"]);
+    }
+
+    #[test]
+    fn test_with_capacity_nonzero() {
+        let mut rb = AllocRingBuffer::with_capacity_nonzero(NonZeroUsize::new(3).unwrap());
+        assert_eq!(rb.capacity(), 3);
+        rb.extend(0..5);
+        assert_eq!(rb.to_vec(), alloc::vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_with_capacity_zeroed() {
+        let mut rb = AllocRingBuffer::<f64>::with_capacity_zeroed(5);
+        assert!(rb.is_full());
+        assert_eq!(rb.len(), 5);
+        assert!(rb.iter().all(|&i| i == 0.0));
+
+        rb.push(1.0);
+        assert_eq!(rb.to_vec(), alloc::vec![0.0, 0.0, 0.0, 0.0, 1.0]);
+
+        let rb = AllocRingBuffer::<[u16; 3]>::with_capacity_zeroed(2);
+        assert_eq!(rb.to_vec(), alloc::vec![[0; 3], [0; 3]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "zero-sized type")]
+    fn test_with_capacity_zeroed_zst() {
+        let _ = AllocRingBuffer::<[u8; 0]>::with_capacity_zeroed(4);
+    }
+
     #[test]
     fn test_conversions() {
         // from &[T]
@@ -1,25 +1,47 @@
 use crate::ringbuffer_trait::{RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator};
-use crate::{AllocRingBuffer, RingBuffer};
-use alloc::collections::VecDeque;
-use core::ops::{Deref, DerefMut, Index, IndexMut};
+use crate::{AllocRingBuffer, OverwritePolicy, RingBuffer};
+use alloc::collections::{TryReserveError, VecDeque};
+use core::ops::{AddAssign, Deref, DerefMut, Index, IndexMut};
 
-/// A growable ringbuffer. Once capacity is reached, the size is doubled.
+/// A growable ringbuffer. Once capacity is reached, the size is doubled, or multiplied by the
+/// factor set with [`set_growth_factor`](GrowableAllocRingBuffer::set_growth_factor).
 /// Wrapper of the built-in [`VecDeque`] struct.
 ///
 /// The reason this is a wrapper, is that we want `RingBuffers` to implement `Index<isize>`,
 /// which we cannot do for remote types like `VecDeque`
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct GrowableAllocRingBuffer<T>(VecDeque<T>);
+///
+/// # Capacity
+/// Unlike the other ringbuffers, this one never overwrites elements, so its length is only
+/// bounded by memory. [`len`](RingBuffer::len) is the number of elements in the buffer, and
+/// [`capacity`](RingBuffer::capacity) is the number of elements that fit in the current
+/// allocation, which is also returned by
+/// [`allocated_capacity`](GrowableAllocRingBuffer::allocated_capacity). The buffer is full
+/// when pushing the next element requires a larger allocation, not when it stops accepting
+/// elements.
+#[derive(Debug, Clone)]
+pub struct GrowableAllocRingBuffer<T>(VecDeque<T>, f64);
+
+/// The growth factor of a new [`GrowableAllocRingBuffer`].
+const DEFAULT_GROWTH_FACTOR: f64 = 2.0;
+
+// the growth factor only tunes allocations, so it doesn't take part in comparisons
+impl<T: PartialEq> PartialEq for GrowableAllocRingBuffer<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for GrowableAllocRingBuffer<T> {}
 
 impl<T, const N: usize> From<[T; N]> for GrowableAllocRingBuffer<T> {
     fn from(value: [T; N]) -> Self {
-        Self(VecDeque::from(value))
+        Self(VecDeque::from(value), DEFAULT_GROWTH_FACTOR)
     }
 }
 
 impl<T> From<VecDeque<T>> for GrowableAllocRingBuffer<T> {
     fn from(value: VecDeque<T>) -> Self {
-        Self(value)
+        Self(value, DEFAULT_GROWTH_FACTOR)
     }
 }
 
@@ -131,13 +153,141 @@ impl<T> GrowableAllocRingBuffer<T> {
     /// Creates an empty ringbuffer.
     #[must_use]
     pub fn new() -> Self {
-        Self(VecDeque::new())
+        Self(VecDeque::new(), DEFAULT_GROWTH_FACTOR)
     }
 
     /// Creates an empty ringbuffer with space for at least capacity elements.
     #[must_use]
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(VecDeque::with_capacity(capacity))
+        Self(VecDeque::with_capacity(capacity), DEFAULT_GROWTH_FACTOR)
+    }
+
+    /// Returns the number of elements the current allocation has room for, which is at least
+    /// [`len`](RingBuffer::len). This is the same as [`capacity`](RingBuffer::capacity), named
+    /// to make clear that it is the memory reserved right now and not an upper bound on the
+    /// length: the allocation grows when a push doesn't fit.
+    #[inline]
+    #[must_use]
+    pub fn allocated_capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Sets the factor by which the capacity is multiplied when a push doesn't fit in the
+    /// current allocation. The default is 2. A smaller factor wastes less memory, at the cost
+    /// of growing, and so moving the elements, more often.
+    ///
+    /// The new capacity is rounded down, but is always large enough for the elements being
+    /// added. When growing by the factor can't be allocated, the buffer only grows as far as
+    /// needed. This applies to [`push`](RingBuffer::push), [`try_push`](Self::try_push),
+    /// [`extend`](Extend::extend) and [`grow_filling`](Self::grow_filling), not to methods of
+    /// the [`VecDeque`] this dereferences to.
+    ///
+    /// # Panics
+    /// Panics when `factor` isn't a finite number greater than 1
+    ///
+    /// ```
+    /// use ringbuffer::{GrowableAllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+    /// rb.set_growth_factor(1.5);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// assert!(rb.allocated_capacity() >= 6);
+    /// ```
+    pub fn set_growth_factor(&mut self, factor: f64) {
+        assert!(
+            factor > 1.0 && factor.is_finite(),
+            "Growth factor must be a finite number greater than 1"
+        );
+        self.1 = factor;
+    }
+
+    /// Returns the factor by which the capacity grows, see
+    /// [`set_growth_factor`](Self::set_growth_factor).
+    #[inline]
+    #[must_use]
+    pub fn growth_factor(&self) -> f64 {
+        self.1
+    }
+
+    /// Returns the capacity to grow to so `additional` more elements fit.
+    fn grown_capacity(&self, additional: usize) -> usize {
+        let required = self.0.len().saturating_add(additional);
+        // float to int casts saturate, so a huge factor can ask for an impossible allocation,
+        // which is why the callers fall back to the required capacity
+        let grown = (self.0.capacity() as f64 * self.1) as usize;
+        required.max(grown)
+    }
+
+    /// Grows the allocation by the growth factor if `additional` more elements don't fit. When
+    /// that much can't be allocated, grows only by what is needed instead.
+    fn try_reserve_for(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let len = self.0.len();
+        if self.0.capacity() - len < additional
+            && self
+                .0
+                .try_reserve_exact(self.grown_capacity(additional) - len)
+                .is_err()
+        {
+            self.0.try_reserve_exact(additional)?;
+        }
+        Ok(())
+    }
+
+    /// Like [`try_reserve_for`](Self::try_reserve_for), but panics or aborts like
+    /// [`VecDeque::reserve_exact`] when even the required capacity can't be allocated.
+    fn reserve_for(&mut self, additional: usize) {
+        if self.try_reserve_for(additional).is_err() {
+            self.0.reserve_exact(additional);
+        }
+    }
+
+    /// Pushes a value onto the buffer, growing it if there is no space left.
+    ///
+    /// Unlike [`push`](RingBuffer::push), which aborts when growing the buffer fails to allocate,
+    /// this returns an error instead. On error, the buffer is left unchanged.
+    ///
+    /// # Errors
+    /// Returns a [`TryReserveError`] when the capacity overflows or the allocator reports a failure.
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        self.try_reserve_for(1)?;
+        self.0.push_back(value);
+        Ok(())
+    }
+
+    /// Moves the elements to the start of the allocation and shrinks it to fit them, releasing
+    /// the memory that was used by elements which were already dequeued. The order of the
+    /// elements is preserved.
+    pub fn compact(&mut self) {
+        let _ = self.0.make_contiguous();
+        self.0.shrink_to_fit();
+    }
+
+    /// Drops all elements, and shrinks or grows the allocation so it holds at least `capacity`
+    /// elements, without keeping much more. This returns a buffer which grew temporarily to a
+    /// baseline size, for example before putting it back into a pool.
+    ///
+    /// Like [`VecDeque::shrink_to`] and [`VecDeque::reserve_exact`], the allocator may leave
+    /// some extra capacity, and an allocation which is already close enough is kept as it is.
+    pub fn reset_to_capacity(&mut self, capacity: usize) {
+        self.0.clear();
+        if self.0.capacity() < capacity {
+            self.0.reserve_exact(capacity);
+        } else {
+            self.0.shrink_to(capacity);
+        }
+    }
+
+    /// Grows the buffer by `additional` elements, which are all clones of `value`.
+    ///
+    /// The new elements are appended at the write end, so they are the newest elements in the
+    /// buffer: they come after the existing elements in iteration order, and are dequeued last.
+    /// This is useful to lengthen a delay line where the new taps should start at a known value.
+    pub fn grow_filling(&mut self, additional: usize, value: T)
+    where
+        T: Clone,
+    {
+        self.reserve_for(additional);
+        self.0.extend(core::iter::repeat(value).take(additional));
     }
 }
 
@@ -187,7 +337,8 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
     }
 
     fn push(&mut self, value: T) {
-        self.push_back(value);
+        self.reserve_for(1);
+        self.0.push_back(value);
     }
 
     fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
@@ -204,6 +355,43 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
         self.0.clear();
     }
 
+    fn push_all_returning_evicted<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> alloc::vec::Vec<T> {
+        self.extend(iter);
+        alloc::vec::Vec::new()
+    }
+
+    fn set_front(&mut self, index: usize) {
+        assert!(index < self.len(), "index out of bounds");
+        self.0.rotate_left(index);
+    }
+
+    fn push_with_policy<P: OverwritePolicy<T>>(&mut self, value: T, _: &mut P) -> bool {
+        // the buffer grows when it is full, so nothing ever needs to be evicted
+        self.push(value);
+        true
+    }
+
+    fn cycle_newest_to_front(&mut self) {
+        if let Some(value) = self.0.pop_back() {
+            self.0.push_front(value);
+        }
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        self.0.as_slices()
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.0.as_mut_slices()
+    }
+
+    fn make_contiguous(&mut self) -> &mut [T] {
+        self.0.make_contiguous()
+    }
+
     fn get(&self, index: usize) -> Option<&T> {
         if self.is_empty() {
             None
@@ -231,11 +419,12 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
         if RingBuffer::ptr_len(rb) == 0 {
             None
         } else if index >= 0 {
-            (*rb).0.get_mut(index as usize)
+            let len = Self::ptr_len(rb);
+            (*rb).0.get_mut(crate::mask_modulo(len, index as usize))
         } else {
             let len = Self::ptr_len(rb);
 
-            let positive_index = -index as usize + 1;
+            let positive_index = -index as usize - 1;
             let masked = crate::mask_modulo(len, positive_index);
             let index = len - 1 - masked;
 
@@ -249,7 +438,8 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
         if RingBuffer::ptr_len(rb) == 0 {
             None
         } else {
-            (*rb).0.get_mut(index)
+            let len = Self::ptr_len(rb);
+            (*rb).0.get_mut(crate::mask_modulo(len, index))
         }
         .map(|i| i as *mut T)
     }
@@ -257,7 +447,57 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
 
 impl<T> Extend<T> for GrowableAllocRingBuffer<T> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        self.0.extend(iter);
+        let iter = iter.into_iter();
+        self.reserve_for(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T> AddAssign<T> for GrowableAllocRingBuffer<T> {
+    /// Pushes `rhs` onto the buffer. See [`push`](RingBuffer::push).
+    ///
+    /// ```
+    /// use ringbuffer::{GrowableAllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = GrowableAllocRingBuffer::new();
+    /// rb += 1;
+    /// rb += 2;
+    /// assert_eq!(rb.to_vec(), vec![1, 2]);
+    /// ```
+    fn add_assign(&mut self, rhs: T) {
+        self.push(rhs);
+    }
+}
+
+impl<T: Clone> AddAssign<&[T]> for GrowableAllocRingBuffer<T> {
+    /// Pushes clones of all elements of `rhs` onto the buffer. See [`extend`](Extend::extend).
+    ///
+    /// ```
+    /// use ringbuffer::{GrowableAllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = GrowableAllocRingBuffer::<i32>::new();
+    /// rb += &[1, 2, 3][..];
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+    /// ```
+    fn add_assign(&mut self, rhs: &[T]) {
+        self.extend(rhs.iter().cloned());
+    }
+}
+
+impl<T> AddAssign<alloc::vec::Vec<T>> for GrowableAllocRingBuffer<T> {
+    /// Pushes all elements of `rhs` onto the buffer. See [`extend`](Extend::extend).
+    ///
+    /// ```
+    /// use ringbuffer::{GrowableAllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = GrowableAllocRingBuffer::<i32>::new();
+    /// rb += vec![1, 2, 3];
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+    /// ```
+    fn add_assign(&mut self, rhs: alloc::vec::Vec<T>) {
+        self.extend(rhs);
     }
 }
 
@@ -277,6 +517,173 @@ impl<T> IndexMut<usize> for GrowableAllocRingBuffer<T> {
 
 impl<T> FromIterator<T> for GrowableAllocRingBuffer<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        Self(VecDeque::from_iter(iter))
+        Self(VecDeque::from_iter(iter), DEFAULT_GROWTH_FACTOR)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GrowableAllocRingBuffer, RingBuffer};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_try_push() {
+        let mut rb = GrowableAllocRingBuffer::with_capacity(2);
+        for i in 0..10 {
+            assert!(rb.try_push(i).is_ok());
+        }
+
+        assert_eq!(rb.len(), 10);
+        assert_eq!(rb.to_vec(), vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_allocated_capacity() {
+        let mut rb = GrowableAllocRingBuffer::with_capacity(2);
+        let initial = rb.allocated_capacity();
+        assert!(initial >= 2);
+        assert_eq!(rb.len(), 0);
+
+        let mut grows = 0;
+        for i in 0..100 {
+            let before = rb.allocated_capacity();
+            rb.push(i);
+            if rb.allocated_capacity() != before {
+                grows += 1;
+            }
+
+            assert_eq!(rb.len(), i + 1);
+            assert_eq!(rb.allocated_capacity(), rb.capacity());
+            assert!(rb.allocated_capacity() >= rb.len());
+        }
+        assert!(grows >= 3);
+        assert!(rb.allocated_capacity() > initial);
+    }
+
+    #[test]
+    fn test_growth_factor() {
+        // the allocator may hand out more than was asked for, so only lower bounds are checked
+        fn check_growth(mut rb: GrowableAllocRingBuffer<i32>, pushes: i32) -> usize {
+            let mut grows = 0;
+            for i in 0..pushes {
+                let before = rb.allocated_capacity();
+                rb.push(i);
+                if rb.allocated_capacity() != before {
+                    let grown = (before as f64 * rb.growth_factor()) as usize;
+                    assert!(rb.allocated_capacity() >= grown.max(rb.len()));
+                    grows += 1;
+                }
+            }
+            assert_eq!(rb.to_vec(), (0..pushes).collect::<Vec<_>>());
+            grows
+        }
+
+        let rb = GrowableAllocRingBuffer::with_capacity(4);
+        assert_eq!(rb.growth_factor(), 2.0);
+        assert!(check_growth(rb, 30) >= 1);
+
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        rb.set_growth_factor(1.5);
+        assert!(check_growth(rb, 30) >= 1);
+
+        // growing by less than one element still makes room
+        let mut rb = GrowableAllocRingBuffer::new();
+        rb.set_growth_factor(1.01);
+        assert!(check_growth(rb, 3) >= 1);
+
+        // extending grows at least as far as the factor says
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        rb.set_growth_factor(3.0);
+        let before = rb.allocated_capacity();
+        rb.extend(0..5);
+        assert!(rb.allocated_capacity() >= (before * 3).max(5));
+        rb.extend(0..20);
+        assert!(rb.allocated_capacity() >= 25);
+        assert!(rb.try_push(0).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_growth_factor_too_small() {
+        GrowableAllocRingBuffer::<i32>::new().set_growth_factor(1.0);
+    }
+
+    #[test]
+    fn test_growth_factor_huge() {
+        // growing by the factor can't be allocated, so the buffer only grows as far as needed
+        let mut rb = GrowableAllocRingBuffer::with_capacity(4);
+        rb.set_growth_factor(1e300);
+        rb.extend(0..5);
+        assert!(rb.allocated_capacity() >= 5);
+        rb.push(5);
+        assert!(rb.try_push(6).is_ok());
+        rb.grow_filling(10, 0);
+        assert_eq!(rb.len(), 17);
+        assert_eq!(rb.to_vec()[..7], [0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_growth_factor_infinite() {
+        GrowableAllocRingBuffer::<i32>::new().set_growth_factor(f64::INFINITY);
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut rb = GrowableAllocRingBuffer::with_capacity(1000);
+        rb.extend(0..1000);
+        for _ in 0..990 {
+            let _ = rb.dequeue();
+        }
+        rb.extend(1000..1005);
+
+        rb.compact();
+        assert!(rb.capacity() < 1000);
+        assert!(rb.capacity() >= 15);
+        assert!(rb.as_slices().1.is_empty());
+        assert_eq!(rb.to_vec(), (990..1005).collect::<Vec<_>>());
+
+        rb.push(1005);
+        assert_eq!(rb.back(), Some(&1005));
+        assert_eq!(rb.len(), 16);
+    }
+
+    #[test]
+    fn test_grow_filling() {
+        let mut rb = GrowableAllocRingBuffer::with_capacity(3);
+        rb.extend([1, 2, 3]);
+        let _ = rb.dequeue();
+
+        rb.grow_filling(4, 0);
+        assert_eq!(rb.len(), 6);
+        assert!(rb.capacity() >= 6);
+        assert_eq!(rb.to_vec(), vec![2, 3, 0, 0, 0, 0]);
+
+        rb.grow_filling(0, 9);
+        assert_eq!(rb.to_vec(), vec![2, 3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_reset_to_capacity() {
+        let mut rb = GrowableAllocRingBuffer::with_capacity(16);
+        let baseline = rb.capacity();
+
+        rb.extend(0..1000);
+        assert!(rb.capacity() >= 1000);
+
+        rb.reset_to_capacity(16);
+        assert!(rb.is_empty());
+        assert!(rb.capacity() >= 16 && rb.capacity() < 1000);
+
+        // resetting to the capacity the buffer already has doesn't reallocate
+        let capacity = rb.capacity();
+        rb.extend(0..3);
+        rb.reset_to_capacity(capacity);
+        assert!(rb.is_empty());
+        assert_eq!(rb.capacity(), capacity);
+
+        rb.reset_to_capacity(baseline * 4);
+        assert!(rb.capacity() >= baseline * 4);
     }
 }
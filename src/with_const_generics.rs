@@ -1,9 +1,13 @@
-use crate::ringbuffer_trait::{RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator};
-use crate::RingBuffer;
+use crate::ringbuffer_trait::{
+    drop_elements, slices_eq, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+};
+use crate::{Lagged, Reader, RingBuffer};
+use core::fmt;
 use core::iter::FromIterator;
 use core::mem;
 use core::mem::MaybeUninit;
-use core::ops::{Index, IndexMut};
+use core::ops::{AddAssign, Index, IndexMut};
+use core::slice;
 
 /// The `ConstGenericRingBuffer` struct is a `RingBuffer` implementation which does not require `alloc` but
 /// uses const generics instead.
@@ -11,6 +15,13 @@ use core::ops::{Index, IndexMut};
 /// [`ConstGenericRingBuffer`] allocates the ringbuffer on the stack, and the size must be known at
 /// compile time through const-generics.
 ///
+/// Because the capacity is part of the type, computing indices into the buffer is constant folded.
+/// When the capacity is known at compile time but the buffer should live on the heap,
+/// [`new_boxed`](Self::new_boxed) allocates it there directly. In the push benchmarks, such a
+/// buffer with a power of two `CAP` pushed about twice as fast as an
+/// [`AllocRingBuffer`](crate::AllocRingBuffer) with the same runtime capacity. Other capacities
+/// wrap around with a modulo, which is slower.
+///
 /// # Example
 /// ```
 /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
@@ -131,7 +142,7 @@ impl<T, const CAP: usize> From<crate::AllocRingBuffer<T>> for ConstGenericRingBu
 
 impl<T, const CAP: usize> Drop for ConstGenericRingBuffer<T, CAP> {
     fn drop(&mut self) {
-        self.drain().for_each(drop);
+        drop_elements(self);
     }
 }
 
@@ -146,16 +157,7 @@ impl<T: Clone, const CAP: usize> Clone for ConstGenericRingBuffer<T, CAP> {
 // We need to manually implement PartialEq because MaybeUninit isn't PartialEq
 impl<T: PartialEq, const CAP: usize> PartialEq for ConstGenericRingBuffer<T, CAP> {
     fn eq(&self, other: &Self) -> bool {
-        if self.len() == other.len() {
-            for (a, b) in self.iter().zip(other.iter()) {
-                if a != b {
-                    return false;
-                }
-            }
-            true
-        } else {
-            false
-        }
+        slices_eq(self.as_slices(), other.as_slices())
     }
 }
 
@@ -188,6 +190,340 @@ impl<T, const CAP: usize> ConstGenericRingBuffer<T, CAP> {
             readptr: 0,
         }
     }
+
+    /// Creates a const generic ringbuffer on the heap. Unlike `Box::new(Self::new())`, this
+    /// doesn't build the buffer on the stack first, so it works for buffers too large for it.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<u8, { 1 << 24 }>::new_boxed();
+    /// rb.push(1);
+    /// assert_eq!(rb.capacity(), 1 << 24);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn new_boxed() -> alloc::boxed::Box<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::ERROR_CAPACITY_IS_NOT_ALLOWED_TO_BE_ZERO;
+
+        let layout = alloc::alloc::Layout::new::<Self>();
+        // Safety: the layout isn't zero-sized, since it holds the pointers. The elements are
+        // `MaybeUninit`, so only the pointers have to be initialized.
+        unsafe {
+            let rb = alloc::alloc::alloc(layout).cast::<Self>();
+            if rb.is_null() {
+                alloc::alloc::handle_alloc_error(layout);
+            }
+            core::ptr::addr_of_mut!((*rb).readptr).write(0);
+            core::ptr::addr_of_mut!((*rb).writeptr).write(0);
+            alloc::boxed::Box::from_raw(rb)
+        }
+    }
+
+    /// Creates a buffer holding clones of the elements of `initial`, oldest first, for when the
+    /// number of elements is only known at runtime but must stay within the capacity.
+    ///
+    /// Unlike converting with `From<&[T]>`, which keeps only the last `CAP` elements, this
+    /// fails when `initial` doesn't fit.
+    ///
+    /// # Errors
+    /// Returns a [`CapacityError`] with the length of `initial` and the capacity when
+    /// `initial` holds more than `CAP` elements.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let rb = ConstGenericRingBuffer::<_, 4>::with_len_bound(&[1, 2]).unwrap();
+    /// assert_eq!(rb.to_vec(), vec![1, 2]);
+    ///
+    /// let err = ConstGenericRingBuffer::<_, 4>::with_len_bound(&[0; 5]).unwrap_err();
+    /// assert_eq!((err.requested, err.max), (5, 4));
+    /// ```
+    pub fn with_len_bound(initial: &[T]) -> Result<Self, CapacityError>
+    where
+        T: Clone,
+    {
+        if initial.len() > CAP {
+            return Err(CapacityError {
+                requested: initial.len(),
+                max: CAP,
+            });
+        }
+        Ok(initial.iter().cloned().collect())
+    }
+
+    /// Rotates the backing array so the oldest element (the one returned by
+    /// [`peek`](RingBuffer::peek)) is stored at an index which is a multiple of `alignment`.
+    ///
+    /// Note that `alignment` is counted in elements, not in bytes. The order of the elements
+    /// is unchanged, only the location in memory is. This takes time proportional to `CAP`.
+    ///
+    /// Moving the elements lowers the read and write pointers. A [`Reader`] created before
+    /// then would skip the elements pushed next, so replace it with a new one from
+    /// [`reader`](ConstGenericRingBuffer::reader).
+    ///
+    /// # Panics
+    /// Panics when `alignment` is zero
+    pub fn align_read_to(&mut self, alignment: usize) {
+        assert_ne!(alignment, 0, "Alignment must be greater than 0");
+
+        let physical = crate::mask_modulo(CAP, self.readptr);
+        let shift = physical % alignment;
+        if shift == 0 {
+            return;
+        }
+
+        self.buf.rotate_left(shift);
+        self.readptr -= shift;
+        self.writeptr -= shift;
+        validate_invariants!(self);
+    }
+
+    /// Copies the newest `min(len, M)` elements into a new buffer with capacity `M`, oldest
+    /// first. Growing keeps every element, shrinking drops the oldest ones.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let rb: ConstGenericRingBuffer<i32, 4> = (1..=4).collect();
+    /// let smaller = rb.to_resized::<2>();
+    /// assert_eq!(smaller.to_vec(), vec![3, 4]);
+    /// ```
+    #[must_use]
+    pub fn to_resized<const M: usize>(&self) -> ConstGenericRingBuffer<T, M>
+    where
+        T: Clone,
+    {
+        let skip = self.len().saturating_sub(M);
+        self.iter().skip(skip).cloned().collect()
+    }
+
+    /// Empties the buffer in constant time, by resetting the read and write pointers.
+    ///
+    /// Unlike [`clear`](RingBuffer::clear), this doesn't visit the elements to drop them, which
+    /// is why it is only available for `Copy` types.
+    ///
+    /// Like `clear`, this resets [`write_generation`](Self::write_generation), so a [`Reader`]
+    /// created before would stop seeing new elements. Replace it with a new one from
+    /// [`reader`](Self::reader).
+    #[inline]
+    pub fn reset(&mut self)
+    where
+        T: Copy,
+    {
+        self.readptr = 0;
+        self.writeptr = 0;
+        validate_invariants!(self);
+    }
+
+    /// Returns the elements in the buffer as two slices, oldest first. This is the same as
+    /// [`as_slices`](RingBuffer::as_slices), named to pair with
+    /// [`write_regions`](ConstGenericRingBuffer::write_regions).
+    #[inline]
+    #[must_use]
+    pub fn read_regions(&self) -> (&[T], &[T]) {
+        self.as_slices()
+    }
+
+    /// Returns the unused capacity of the buffer as two slices of uninitialized elements, in the
+    /// order in which they will be written. Together they are `CAP - len()` long.
+    ///
+    /// This allows filling the buffer without going through [`push`](RingBuffer::push), for
+    /// example by a DMA transfer into both regions. Afterwards, call
+    /// [`advance_write`](ConstGenericRingBuffer::advance_write) to add the written elements to the
+    /// buffer. Likewise, [`read_regions`](ConstGenericRingBuffer::read_regions) and
+    /// [`advance_read`](ConstGenericRingBuffer::advance_read) consume elements in bulk.
+    pub fn write_regions(&mut self) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let start = crate::mask_modulo(CAP, self.writeptr);
+        let (first_len, second_len) = self.spare_regions();
+
+        // the spare region directly follows the live elements, so it wraps around at most once
+        let (head, tail) = self.buf.split_at_mut(start);
+        (&mut tail[..first_len], &mut head[..second_len])
+    }
+
+    /// Returns the lengths of the two regions returned by
+    /// [`write_regions`](ConstGenericRingBuffer::write_regions), without borrowing them. This
+    /// tells how a write of a given size is split across the end of the storage.
+    #[must_use]
+    pub fn spare_regions(&self) -> (usize, usize) {
+        let spare = CAP - self.len();
+        let start = crate::mask_modulo(CAP, self.writeptr);
+        let first_len = spare.min(CAP - start);
+        (first_len, spare - first_len)
+    }
+
+    /// Adds the first `n` elements of the [`write_regions`](ConstGenericRingBuffer::write_regions)
+    /// to the buffer, as the newest elements.
+    ///
+    /// # Safety
+    /// The first `n` elements of the write regions must have been initialized.
+    ///
+    /// # Panics
+    /// Panics when `n` is larger than the unused capacity of the buffer
+    pub unsafe fn advance_write(&mut self, n: usize) {
+        assert!(
+            n <= CAP - self.len(),
+            "Can't advance past the unused capacity"
+        );
+        self.writeptr += n;
+        validate_invariants!(self);
+    }
+
+    /// Hands the first of the [`write_regions`](ConstGenericRingBuffer::write_regions) to `f`, which writes
+    /// elements to the start of it and returns how many it wrote. These are then added to the
+    /// buffer as the newest elements, like [`advance_write`](ConstGenericRingBuffer::advance_write) does.
+    ///
+    /// This covers the common case of a single write into the unused capacity. The region ends
+    /// where the storage ends, so it may be shorter than the unused capacity.
+    ///
+    /// # Safety
+    /// `f` must initialize the first `n` elements of the region, where `n` is the value it
+    /// returns.
+    ///
+    /// # Panics
+    /// Panics when `f` returns more than the length of the region
+    pub unsafe fn with_write_region<F: FnOnce(&mut [MaybeUninit<T>]) -> usize>(&mut self, f: F) {
+        let region = self.write_regions().0;
+        let len = region.len();
+        let n = f(region);
+        assert!(n <= len, "Can't write past the end of the region");
+        self.advance_write(n);
+    }
+
+    /// Removes the `n` oldest elements from the buffer, after they were read through the
+    /// [`read_regions`](ConstGenericRingBuffer::read_regions).
+    ///
+    /// # Panics
+    /// Panics when `n` is larger than the length of the buffer
+    pub fn advance_read(&mut self, n: usize) {
+        assert!(n <= self.len(), "Can't advance past the end of the buffer");
+        for _ in 0..n {
+            self.skip();
+        }
+        validate_invariants!(self);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<const CAP: usize> ConstGenericRingBuffer<u8, CAP> {
+    /// Reads at most `max` bytes from `reader` directly into the unused capacity of the buffer,
+    /// without an intermediate buffer. Returns the number of bytes read, which were added as the
+    /// newest elements.
+    ///
+    /// Like [`Read::read`](std::io::Read::read), this doesn't wait for `max` bytes to become
+    /// available. When the unused capacity wraps around, a second read fills the part at the
+    /// start of the storage if the first one filled the part at the end. Nothing is overwritten:
+    /// when the buffer is full, this reads nothing and returns 0.
+    ///
+    /// # Errors
+    /// Returns the error of `reader`, unless some bytes were read already. Reads which are
+    /// interrupted are retried. A reader which claims to have read more bytes than it was given
+    /// room for is treated as failing with [`InvalidData`](std::io::ErrorKind::InvalidData).
+    pub fn fill_from_reader<R: std::io::Read>(
+        &mut self,
+        reader: &mut R,
+        max: usize,
+    ) -> std::io::Result<usize> {
+        let n = crate::byte_io::read_into_regions(reader, self.write_regions(), max)?;
+        // Safety: the first n bytes of the write regions were initialized by the read
+        unsafe { self.advance_write(n) };
+        Ok(n)
+    }
+
+    /// Writes at most `max` of the oldest bytes to `writer`, and removes the bytes which were
+    /// written from the buffer. Returns the number of bytes written.
+    ///
+    /// Like [`Write::write`](std::io::Write::write), this doesn't guarantee that `max` bytes are
+    /// written. When the bytes wrap around, a second write sends the part at the start of the
+    /// storage if the first one was written completely. After a short write, only the bytes
+    /// that were accepted by `writer` are removed.
+    ///
+    /// # Errors
+    /// Returns the error of `writer`, unless some bytes were written already. Writes which are
+    /// interrupted are retried. A writer which claims to have written more bytes than it was
+    /// given is treated as failing with [`InvalidData`](std::io::ErrorKind::InvalidData).
+    pub fn flush_to_writer<W: std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        max: usize,
+    ) -> std::io::Result<usize> {
+        let n = crate::byte_io::write_from_regions(writer, self.read_regions(), max)?;
+        self.advance_read(n);
+        Ok(n)
+    }
+}
+
+impl<T, const CAP: usize> ConstGenericRingBuffer<T, CAP> {
+    /// Returns the number of elements pushed since the buffer was created or last cleared,
+    /// including elements that were overwritten or dequeued since.
+    ///
+    /// Comparing this with an earlier value tells whether the buffer has advanced since. An
+    /// element that was the `n`th one ever pushed is still in the buffer when
+    /// `write_generation() - n < len()`.
+    ///
+    /// The counter is reset by [`clear`](RingBuffer::clear) and by methods built on it, like
+    /// [`fill_with`](RingBuffer::fill_with), as well as by [`reset`](Self::reset), and lowered
+    /// by `align_read_to`. It is never renormalized, so after `usize::MAX` pushes it overflows
+    /// like the internal write index.
+    #[inline]
+    #[must_use]
+    pub fn write_generation(&self) -> usize {
+        self.writeptr
+    }
+
+    /// Creates a [`Reader`] which observes all elements pushed from now on,
+    /// without removing them from the buffer.
+    #[inline]
+    #[must_use]
+    pub fn reader(&self) -> Reader<Self> {
+        Reader::new(self.writeptr)
+    }
+}
+
+impl<T, const CAP: usize> Reader<ConstGenericRingBuffer<T, CAP>> {
+    /// Returns the next element this reader has not seen yet, or `None` when it is up to date.
+    ///
+    /// When the reader lagged behind, it skips to the oldest element still in the buffer.
+    /// Use [`lagged_by`](Reader::lagged_by) to find out how many elements were missed.
+    pub fn next<'a>(&mut self, rb: &'a ConstGenericRingBuffer<T, CAP>) -> Option<&'a T> {
+        self.position = self.position.max(rb.readptr);
+        if self.position >= rb.writeptr {
+            return None;
+        }
+
+        // Safety: readptr <= position < writeptr, so this element is initialized
+        let res = unsafe { get_unchecked(rb, crate::mask_modulo(CAP, self.position)) };
+        self.position += 1;
+        Some(res)
+    }
+
+    /// Returns the number of elements that were overwritten before this reader saw them.
+    #[must_use]
+    pub fn lagged_by(&self, rb: &ConstGenericRingBuffer<T, CAP>) -> usize {
+        rb.readptr.saturating_sub(self.position)
+    }
+
+    /// Returns the next element this reader has not seen yet, or `None` when it is up to date,
+    /// like [`next`](Reader::next). Unlike `next`, this doesn't silently skip elements which
+    /// were overwritten.
+    ///
+    /// # Errors
+    /// Returns [`Lagged`] with the number of missed elements when the reader lagged behind.
+    /// The reader then moves on to the oldest element still in the buffer, which the next call
+    /// returns.
+    pub fn read<'a>(
+        &mut self,
+        rb: &'a ConstGenericRingBuffer<T, CAP>,
+    ) -> Result<Option<&'a T>, Lagged> {
+        let missed = self.lagged_by(rb);
+        if missed > 0 {
+            self.position = rb.readptr;
+            return Err(Lagged { missed });
+        }
+        Ok(self.next(rb))
+    }
 }
 
 /// Get a reference from the buffer without checking it is initialized
@@ -251,6 +587,54 @@ impl<T, const CAP: usize> Extend<T> for ConstGenericRingBuffer<T, CAP> {
     }
 }
 
+impl<T, const CAP: usize> AddAssign<T> for ConstGenericRingBuffer<T, CAP> {
+    /// Pushes `rhs` onto the buffer. See [`push`](RingBuffer::push).
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 2>::new();
+    /// rb += 1;
+    /// rb += 2;
+    /// rb += 3;
+    /// assert_eq!(rb.to_vec(), vec![2, 3]);
+    /// ```
+    fn add_assign(&mut self, rhs: T) {
+        self.push(rhs);
+    }
+}
+
+impl<T: Clone, const CAP: usize> AddAssign<&[T]> for ConstGenericRingBuffer<T, CAP> {
+    /// Pushes clones of all elements of `rhs` onto the buffer. See [`extend`](Extend::extend).
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+    /// rb += &[1, 2, 3, 4][..];
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    fn add_assign(&mut self, rhs: &[T]) {
+        self.extend(rhs.iter().cloned());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const CAP: usize> AddAssign<alloc::vec::Vec<T>> for ConstGenericRingBuffer<T, CAP> {
+    /// Pushes all elements of `rhs` onto the buffer. See [`extend`](Extend::extend).
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+    /// rb += vec![1, 2, 3, 4];
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    fn add_assign(&mut self, rhs: alloc::vec::Vec<T>) {
+        self.extend(rhs);
+    }
+}
+
 unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP> {
     #[inline]
     unsafe fn ptr_capacity(_: *const Self) -> usize {
@@ -283,6 +667,7 @@ unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP
         let index = crate::mask_modulo(CAP, self.writeptr);
         self.buf[index] = MaybeUninit::new(value);
         self.writeptr += 1;
+        validate_invariants!(self);
     }
 
     fn dequeue(&mut self) -> Option<T> {
@@ -292,6 +677,7 @@ unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP
             let index = crate::mask_modulo(CAP, self.readptr);
             let res = mem::replace(&mut self.buf[index], MaybeUninit::uninit());
             self.readptr += 1;
+            validate_invariants!(self);
 
             // Safety: the fact that we got this maybeuninit from the buffer (with mask) means that
             // it's initialized. If it wasn't the is_empty call would have caught it. Values
@@ -308,12 +694,58 @@ unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP
         crate::mask_modulo
     );
 
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let len = self.len();
+        let start = crate::mask_modulo(CAP, self.readptr);
+        let first_len = len.min(CAP - start);
+        let buf = self.buf.as_ptr().cast::<T>();
+
+        // Safety: the first slice runs from readptr up to at most the end of the array,
+        // the second one continues at the start of the array. Both only cover
+        // initialized elements.
+        unsafe {
+            (
+                slice::from_raw_parts(buf.add(start), first_len),
+                slice::from_raw_parts(buf, len - first_len),
+            )
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let len = self.len();
+        let start = crate::mask_modulo(CAP, self.readptr);
+        let first_len = len.min(CAP - start);
+        let buf = self.buf.as_mut_ptr().cast::<T>();
+
+        // Safety: see as_slices. The two slices never overlap since len <= CAP.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(buf.add(start), first_len),
+                slice::from_raw_parts_mut(buf, len - first_len),
+            )
+        }
+    }
+
+    fn make_contiguous(&mut self) -> &mut [T] {
+        if !self.is_contiguous() {
+            self.align_read_to(CAP);
+        }
+        self.as_mut_slices().0
+    }
+
     #[inline]
     fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
         self.clear();
         self.readptr = 0;
-        self.writeptr = CAP;
-        self.buf.fill_with(|| MaybeUninit::new(f()));
+        self.writeptr = 0;
+
+        // writeptr is only advanced after each write, so if f panics only the elements
+        // written so far get dropped
+        while self.writeptr < CAP {
+            self.buf[self.writeptr] = MaybeUninit::new(f());
+            self.writeptr += 1;
+        }
+        validate_invariants!(self);
     }
 }
 
@@ -352,10 +784,45 @@ impl<T, const CAP: usize> IndexMut<usize> for ConstGenericRingBuffer<T, CAP> {
     }
 }
 
+/// The error returned by [`ConstGenericRingBuffer::with_len_bound`] when there are more
+/// elements than fit in the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The number of elements that were passed.
+    pub requested: usize,
+    /// The capacity of the buffer.
+    pub max: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} elements don't fit in a ringbuffer with capacity {}",
+            self.requested, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_new_boxed() {
+        // too large for the stack of a test thread
+        let mut rb = ConstGenericRingBuffer::<u64, { 1 << 20 }>::new_boxed();
+        assert!(rb.is_empty());
+        rb.extend(0..(1 << 20) + 3);
+        assert!(rb.is_full());
+        assert_eq!(rb.peek(), Some(&3));
+        assert_eq!(rb.back(), Some(&((1 << 20) + 2)));
+    }
+
     #[test]
     fn test_not_power_of_two() {
         let mut rb = ConstGenericRingBuffer::<usize, 10>::new();
@@ -370,10 +837,10 @@ mod tests {
             assert!(rb.is_full());
 
             for i in 0..10 {
-                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue())
+                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue());
             }
 
-            assert!(rb.is_empty())
+            assert!(rb.is_empty());
         }
     }
 
@@ -384,6 +851,99 @@ mod tests {
         let _ = b[2];
     }
 
+    #[test]
+    fn test_reset() {
+        let mut rb = ConstGenericRingBuffer::<u32, 4>::new();
+        rb.extend(0..6);
+        rb.reset();
+        assert!(rb.is_empty());
+
+        rb.extend(0..2);
+        assert_eq!(rb.read_regions(), (&[0, 1][..], &[][..]));
+    }
+
+    #[test]
+    fn test_regions() {
+        let mut rb = ConstGenericRingBuffer::<u8, 5>::new();
+        {
+            let (a, b) = rb.write_regions();
+            assert_eq!((a.len(), b.len()), (5, 0));
+            a[0] = MaybeUninit::new(1);
+            a[1] = MaybeUninit::new(2);
+        }
+        unsafe { rb.advance_write(2) };
+        assert_eq!(rb.read_regions(), (&[1, 2][..], &[][..]));
+
+        rb.extend([3, 4]);
+        rb.advance_read(3);
+        assert_eq!(rb.read_regions(), (&[4][..], &[][..]));
+
+        // the spare capacity now wraps around the end of the array
+        {
+            let (a, b) = rb.write_regions();
+            assert_eq!((a.len(), b.len()), (1, 3));
+            for (i, slot) in a.iter_mut().chain(b.iter_mut()).enumerate() {
+                *slot = MaybeUninit::new(10 + i as u8);
+            }
+        }
+        unsafe { rb.advance_write(4) };
+        assert!(rb.is_full());
+        assert_eq!(rb.read_regions(), (&[4, 10][..], &[11, 12, 13][..]));
+    }
+
+    #[test]
+    fn test_spare_regions() {
+        let mut rb = ConstGenericRingBuffer::<u8, 5>::new();
+        assert_eq!(rb.spare_capacity_len(), 5);
+        assert_eq!(rb.spare_regions(), (5, 0));
+
+        rb.extend(0..3);
+        rb.advance_read(2);
+        assert_eq!(rb.spare_capacity_len(), 4);
+        assert_eq!(rb.spare_regions(), (2, 2));
+
+        rb.extend(3..7);
+        assert!(rb.is_full());
+        assert_eq!(rb.spare_capacity_len(), 0);
+        assert_eq!(rb.spare_regions(), (0, 0));
+    }
+
+    #[test]
+    fn test_write_generation() {
+        let mut rb = ConstGenericRingBuffer::<usize, 3>::new();
+        assert_eq!(rb.write_generation(), 0);
+
+        for i in 1..=10 {
+            rb.push(i);
+            assert_eq!(rb.write_generation(), i);
+
+            // an element pushed as the nth one is still present while the difference is small
+            assert_eq!(rb.back(), Some(&i));
+            assert!(rb.write_generation() - i < rb.len());
+        }
+
+        let _ = rb.dequeue();
+        assert_eq!(rb.write_generation(), 10);
+    }
+
+    #[test]
+    fn test_new_without_default_elements() {
+        extern crate std;
+        use std::string::String;
+
+        struct NoDefault(String);
+
+        let mut rb = ConstGenericRingBuffer::<String, 4>::new();
+        rb.push(String::from("a"));
+        rb.push(String::from("b"));
+        assert_eq!(rb.dequeue().as_deref(), Some("a"));
+
+        let mut rb: ConstGenericRingBuffer<NoDefault, 2> = ConstGenericRingBuffer::default();
+        rb.push(NoDefault(String::from("c")));
+        assert_eq!(rb.len(), 1);
+        assert_eq!(rb[0].0, "c");
+    }
+
     #[test]
     fn test_extend() {
         let mut buf = ConstGenericRingBuffer::<u8, 4>::new();
@@ -418,8 +978,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_align_read_to() {
+        let mut rb = ConstGenericRingBuffer::<i32, 10>::new();
+        for i in 0..15 {
+            rb.push(i);
+        }
+        rb.skip();
+        assert_eq!(crate::mask_modulo(10, rb.readptr), 6);
+
+        rb.align_read_to(4);
+        assert_eq!(crate::mask_modulo(10, rb.readptr) % 4, 0);
+        assert_eq!(rb.to_vec(), alloc::vec![6, 7, 8, 9, 10, 11, 12, 13, 14]);
+
+        rb.push(15);
+        rb.push(16);
+        assert_eq!(
+            rb.to_vec(),
+            alloc::vec![7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+        );
+    }
+
+    #[test]
+    fn test_dequeue_array() {
+        let mut rb = ConstGenericRingBuffer::<u8, 4>::new();
+        rb.extend(0..6);
+
+        let (batch, count) = rb.dequeue_array::<3>();
+        assert_eq!(count, 3);
+        assert_eq!(batch, [Some(2), Some(3), Some(4)]);
+
+        let (batch, count) = rb.dequeue_array::<3>();
+        assert_eq!(count, 1);
+        assert_eq!(batch, [Some(5), None, None]);
+        assert!(rb.is_empty());
+
+        assert_eq!(rb.dequeue_array::<2>(), ([None, None], 0));
+    }
+
+    #[test]
+    fn test_collect_keeps_tail() {
+        let rb: ConstGenericRingBuffer<i32, 4> = (0..10).collect();
+        assert!(rb.is_full());
+        assert_eq!(rb.to_vec(), alloc::vec![6, 7, 8, 9]);
+
+        let rb: ConstGenericRingBuffer<i32, 4> = (0..2).collect();
+        assert_eq!(rb.to_vec(), alloc::vec![0, 1]);
+    }
+
+    #[test]
+    fn test_with_len_bound() {
+        let rb = ConstGenericRingBuffer::<i32, 4>::with_len_bound(&[1, 2, 3]).unwrap();
+        assert_eq!(rb.to_vec(), alloc::vec![1, 2, 3]);
+
+        let rb = ConstGenericRingBuffer::<i32, 4>::with_len_bound(&[1, 2, 3, 4]).unwrap();
+        assert!(rb.is_full());
+
+        let rb = ConstGenericRingBuffer::<i32, 4>::with_len_bound(&[]).unwrap();
+        assert!(rb.is_empty());
+
+        let err = ConstGenericRingBuffer::<i32, 4>::with_len_bound(&[1, 2, 3, 4, 5]).unwrap_err();
+        assert_eq!(
+            err,
+            CapacityError {
+                requested: 5,
+                max: 4
+            }
+        );
+    }
+
+    #[test]
+    fn test_to_resized() {
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.extend(0..6);
+
+        let grown = rb.to_resized::<8>();
+        assert_eq!(grown.capacity(), 8);
+        assert_eq!(grown.to_vec(), alloc::vec![2, 3, 4, 5]);
+
+        let shrunk = rb.to_resized::<3>();
+        assert!(shrunk.is_full());
+        assert_eq!(shrunk.to_vec(), alloc::vec![3, 4, 5]);
+
+        // the original is left untouched
+        assert_eq!(rb.to_vec(), alloc::vec![2, 3, 4, 5]);
+        assert!(ConstGenericRingBuffer::<i32, 4>::new()
+            .to_resized::<2>()
+            .is_empty());
+    }
+
     #[cfg(test)]
-    mod tests {
+    mod conversions {
         use crate::{AllocRingBuffer, ConstGenericRingBuffer, GrowableAllocRingBuffer, RingBuffer};
         use alloc::collections::{LinkedList, VecDeque};
         use alloc::string::ToString;
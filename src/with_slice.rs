@@ -0,0 +1,357 @@
+use crate::ringbuffer_trait::{
+    drop_elements, slices_eq, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+};
+use crate::RingBuffer;
+use core::mem;
+use core::mem::MaybeUninit;
+use core::ops::{Index, IndexMut};
+use core::slice;
+
+/// The `SliceRingBuffer` struct is a `RingBuffer` implementation which stores its elements in a
+/// slice provided by the caller, and therefore never allocates.
+///
+/// The capacity of the buffer is the length of the slice. This makes it possible to keep the
+/// storage in a `static` or on the stack, while picking the capacity at runtime. Elements still
+/// in the buffer are dropped when the `SliceRingBuffer` is dropped, the storage itself is not.
+///
+/// # Example
+/// ```
+/// use core::mem::MaybeUninit;
+/// use ringbuffer::{RingBuffer, SliceRingBuffer};
+///
+/// let mut storage = [MaybeUninit::<i32>::uninit(); 2];
+/// let mut buffer = SliceRingBuffer::new(&mut storage);
+///
+/// buffer.push(5);
+/// buffer.push(42);
+/// assert!(buffer.is_full());
+///
+/// // Because capacity is reached the next push will be the first item of the buffer.
+/// buffer.push(1);
+/// assert_eq!(buffer.to_vec(), vec![42, 1]);
+/// ```
+#[derive(Debug)]
+pub struct SliceRingBuffer<'a, T> {
+    buf: &'a mut [MaybeUninit<T>],
+    readptr: usize,
+    writeptr: usize,
+}
+
+impl<'a, T> SliceRingBuffer<'a, T> {
+    /// Creates an empty `SliceRingBuffer` which uses `buf` as its storage.
+    /// The capacity of the buffer is the length of `buf`.
+    ///
+    /// # Panics
+    /// Panics when `buf` is empty
+    #[inline]
+    #[must_use]
+    pub fn new(buf: &'a mut [MaybeUninit<T>]) -> Self {
+        assert!(!buf.is_empty(), "Capacity must be greater than 0");
+
+        Self {
+            buf,
+            readptr: 0,
+            writeptr: 0,
+        }
+    }
+}
+
+impl<T> Drop for SliceRingBuffer<'_, T> {
+    fn drop(&mut self) {
+        drop_elements(self);
+    }
+}
+
+impl<T: PartialEq> PartialEq for SliceRingBuffer<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        slices_eq(self.as_slices(), other.as_slices())
+    }
+}
+
+impl<T: PartialEq> Eq for SliceRingBuffer<'_, T> {}
+
+/// Wraps `index` into a buffer of `cap` elements. The capacity is only known at runtime, but
+/// when it happens to be a power of two the much cheaper bitmask can still be used.
+#[inline]
+fn mask(cap: usize, index: usize) -> usize {
+    if cap.is_power_of_two() {
+        crate::mask_and(cap, index)
+    } else {
+        crate::mask_modulo(cap, index)
+    }
+}
+
+/// Get a reference from the buffer without checking it is initialized
+/// Caller MUST be sure this index is initialized, or undefined behavior will happen
+unsafe fn get_unchecked<'b, T>(rb: *const SliceRingBuffer<'_, T>, index: usize) -> &'b T {
+    (*rb).buf[index]
+        .as_ptr()
+        .as_ref()
+        .expect("slice ptr shouldn't be null!")
+}
+
+/// Get a mutable reference from the buffer without checking it is initialized
+/// Caller MUST be sure this index is initialized, or undefined behavior will happen
+unsafe fn get_unchecked_mut<T>(rb: *mut SliceRingBuffer<'_, T>, index: usize) -> *mut T {
+    (*rb).buf[index]
+        .as_mut_ptr()
+        .as_mut()
+        .expect("slice ptr shouldn't be null!")
+}
+
+impl<'a, T> IntoIterator for SliceRingBuffer<'a, T> {
+    type Item = T;
+    type IntoIter = RingBufferIntoIterator<T, Self>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        RingBufferIntoIterator::new(self)
+    }
+}
+
+impl<'b, 'a: 'b, T> IntoIterator for &'b SliceRingBuffer<'a, T> {
+    type Item = &'b T;
+    type IntoIter = RingBufferIterator<'b, T, SliceRingBuffer<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'b, 'a: 'b, T> IntoIterator for &'b mut SliceRingBuffer<'a, T> {
+    type Item = &'b mut T;
+    type IntoIter = RingBufferMutIterator<'b, T, SliceRingBuffer<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Extend<T> for SliceRingBuffer<'_, T> {
+    fn extend<A: IntoIterator<Item = T>>(&mut self, iter: A) {
+        for i in iter {
+            self.push(i);
+        }
+    }
+}
+
+unsafe impl<T> RingBuffer<T> for SliceRingBuffer<'_, T> {
+    #[inline]
+    unsafe fn ptr_capacity(rb: *const Self) -> usize {
+        (&*rb).buf.len()
+    }
+
+    #[inline]
+    unsafe fn ptr_buffer_size(rb: *const Self) -> usize {
+        (&*rb).buf.len()
+    }
+
+    impl_ringbuffer!(readptr, writeptr);
+
+    #[inline]
+    fn push(&mut self, value: T) {
+        let cap = self.buf.len();
+        if self.is_full() {
+            let previous_value = mem::replace(
+                &mut self.buf[mask(cap, self.readptr)],
+                MaybeUninit::uninit(),
+            );
+            // SAFETY: the buffer is full, so this must be initialized
+            unsafe {
+                drop(previous_value.assume_init());
+            }
+            self.readptr += 1;
+        }
+        let index = mask(cap, self.writeptr);
+        self.buf[index] = MaybeUninit::new(value);
+        self.writeptr += 1;
+        validate_invariants!(self);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            let index = mask(self.buf.len(), self.readptr);
+            let res = mem::replace(&mut self.buf[index], MaybeUninit::uninit());
+            self.readptr += 1;
+            validate_invariants!(self);
+
+            // Safety: the buffer is not empty, so the element at readptr is initialized
+            unsafe { Some(res.assume_init()) }
+        }
+    }
+
+    impl_ringbuffer_ext!(get_unchecked, get_unchecked_mut, readptr, writeptr, mask);
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let len = self.len();
+        let cap = self.buf.len();
+        let start = mask(cap, self.readptr);
+        let first_len = len.min(cap - start);
+        let buf = self.buf.as_ptr().cast::<T>();
+
+        // Safety: the first slice runs from readptr up to at most the end of the slice,
+        // the second one continues at the start of the slice. Both only cover
+        // initialized elements.
+        unsafe {
+            (
+                slice::from_raw_parts(buf.add(start), first_len),
+                slice::from_raw_parts(buf, len - first_len),
+            )
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let len = self.len();
+        let cap = self.buf.len();
+        let start = mask(cap, self.readptr);
+        let first_len = len.min(cap - start);
+        let buf = self.buf.as_mut_ptr().cast::<T>();
+
+        // Safety: see as_slices. The two slices never overlap since len <= cap.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(buf.add(start), first_len),
+                slice::from_raw_parts_mut(buf, len - first_len),
+            )
+        }
+    }
+
+    fn make_contiguous(&mut self) -> &mut [T] {
+        if !self.is_contiguous() {
+            // rotating the slots as MaybeUninit only moves bytes around
+            let shift = mask(self.buf.len(), self.readptr);
+            self.buf.rotate_left(shift);
+            self.readptr -= shift;
+            self.writeptr -= shift;
+            validate_invariants!(self);
+        }
+        self.as_mut_slices().0
+    }
+
+    #[inline]
+    fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
+        self.clear();
+        self.readptr = 0;
+        self.writeptr = 0;
+
+        // writeptr is only advanced after each write, so if f panics only the elements
+        // written so far get dropped
+        while self.writeptr < self.buf.len() {
+            self.buf[self.writeptr] = MaybeUninit::new(f());
+            self.writeptr += 1;
+        }
+        validate_invariants!(self);
+    }
+}
+
+impl<T> Index<usize> for SliceRingBuffer<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for SliceRingBuffer<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::rc::Rc;
+    use std::vec;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_push_dequeue_wrapping() {
+        let mut storage = [MaybeUninit::<usize>::uninit(); 3];
+        let mut rb = SliceRingBuffer::new(&mut storage);
+        assert_eq!(rb.capacity(), 3);
+
+        for round in 0..10 {
+            rb.push(round);
+            rb.push(round + 100);
+            assert_eq!(rb.dequeue(), Some(round));
+            assert_eq!(rb.dequeue(), Some(round + 100));
+            assert!(rb.is_empty());
+        }
+
+        rb.extend(0..5);
+        assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+        assert_eq!(rb.get_signed(-1), Some(&4));
+        assert_eq!(rb.as_slices().0.len() + rb.as_slices().1.len(), 3);
+        assert_eq!(rb.drain().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    fn test_external_storage_is_reused() {
+        static mut STORAGE: [MaybeUninit<u8>; 4] = [MaybeUninit::uninit(); 4];
+
+        // Safety: this test is the only user of STORAGE
+        let storage = unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) };
+        let mut rb = SliceRingBuffer::new(storage);
+        rb.extend(1..=6);
+        assert_eq!(rb.to_vec(), vec![3, 4, 5, 6]);
+        drop(rb);
+
+        let storage = unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) };
+        let mut rb = SliceRingBuffer::new(storage);
+        assert!(rb.is_empty());
+        rb.push(7);
+        assert_eq!(rb.to_vec(), vec![7]);
+    }
+
+    #[test]
+    fn test_drops_contents() {
+        let value = Rc::new(());
+        let mut storage: [MaybeUninit<Rc<()>>; 2] = [MaybeUninit::uninit(), MaybeUninit::uninit()];
+        let mut rb = SliceRingBuffer::new(&mut storage);
+
+        rb.extend(core::iter::repeat(value.clone()).take(3));
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(rb);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_capacity_is_power_of_two() {
+        // the slice length picks between the bitmask and the modulo at runtime
+        for (len, expected) in [(1, true), (3, false), (4, true), (6, false), (8, true)] {
+            let mut storage: Vec<MaybeUninit<i32>> =
+                (0..len).map(|_| MaybeUninit::uninit()).collect();
+            let mut rb = SliceRingBuffer::new(&mut storage);
+            assert_eq!(rb.capacity_is_power_of_two(), expected);
+
+            rb.extend(0..10);
+            assert_eq!(rb.to_vec(), (10 - len..10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        let mut storage = [MaybeUninit::<i32>::uninit(); 5];
+        let mut rb = SliceRingBuffer::new(&mut storage);
+        rb.extend(0..8);
+        assert!(!rb.is_contiguous());
+
+        assert_eq!(rb.make_contiguous(), &mut [3, 4, 5, 6, 7]);
+        assert!(rb.is_contiguous());
+        rb.push(8);
+        assert_eq!(rb.to_vec(), vec![4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_empty_storage() {
+        let mut storage: [MaybeUninit<i32>; 0] = [];
+        let _ = SliceRingBuffer::new(&mut storage);
+    }
+}
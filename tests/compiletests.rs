@@ -6,10 +6,12 @@ use std::path::PathBuf;
 mod conversions;
 
 fn run_mode(mode: &'static str) {
-    let mut config = compiletest::Config::default();
+    let mut config = compiletest::Config {
+        mode: mode.parse().expect("Invalid mode"),
+        src_base: PathBuf::from(format!("tests/{}", mode)),
+        ..Default::default()
+    };
 
-    config.mode = mode.parse().expect("Invalid mode");
-    config.src_base = PathBuf::from(format!("tests/{}", mode));
     config.link_deps(); // Populate config.target_rustcflags with dependencies on the path
     config.clean_rmeta(); // If your tests import the parent crate, this helps with E0464
 
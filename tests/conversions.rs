@@ -43,8 +43,8 @@ convert_tests!(
         alloc_from_const_slice: {let a: &[char; 2] = &['1', '2']; a},
         alloc_from_arr: {let a: [char; 2] = ['1', '2']; a},
 
-        alloc_from_cgrb: {let a = ConstGenericRingBuffer::from(['1', '2']); a},
-        alloc_from_garb: {let a = GrowableAllocRingBuffer::from(['1', '2']); a},
+        alloc_from_cgrb: ConstGenericRingBuffer::from(['1', '2']),
+        alloc_from_garb: GrowableAllocRingBuffer::from(['1', '2']),
     ] => AllocRingBuffer::<_>
 );
 
@@ -59,8 +59,8 @@ convert_tests!(
         growable_alloc_from_const_slice: {let a: &[char; 2] = &['1', '2']; a},
         growable_alloc_from_arr: {let a: [char; 2] = ['1', '2']; a},
 
-        growable_alloc_from_cgrb: {let a = ConstGenericRingBuffer::from(['1', '2']); a},
-        growable_alloc_from_arb: {let a = AllocRingBuffer::from(['1', '2']); a},
+        growable_alloc_from_cgrb: ConstGenericRingBuffer::from(['1', '2']),
+        growable_alloc_from_arb: AllocRingBuffer::from(['1', '2']),
     ] => GrowableAllocRingBuffer::<_>
 );
 
@@ -75,8 +75,8 @@ convert_tests!(
         const_from_const_slice: {let a: &[char; 2] = &['1', '2']; a},
         const_from_arr: {let a: [char; 2] = ['1', '2']; a},
 
-        const_from_garb: {let a = GrowableAllocRingBuffer::from(['1', '2']); a},
-        const_from_arb: {let a = AllocRingBuffer::from(['1', '2']); a},
+        const_from_garb: GrowableAllocRingBuffer::from(['1', '2']),
+        const_from_arb: AllocRingBuffer::from(['1', '2']),
     ] => ConstGenericRingBuffer::<_, 2>
 );
 
@@ -133,3 +133,55 @@ fn test_const_generic_new_parameter() {
     let mut a = ConstGenericRingBuffer::<i32, 50>::new();
     a.push(5);
 }
+
+#[test]
+fn test_const_alloc_round_trip() {
+    let mut a = ConstGenericRingBuffer::<i32, 4>::new();
+    a.extend(0..6);
+
+    let b = AllocRingBuffer::from(a);
+    assert_eq!(b.capacity(), 4);
+    assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+
+    let c = ConstGenericRingBuffer::<i32, 4>::from(b);
+    assert_eq!(c.to_vec(), vec![2, 3, 4, 5]);
+
+    let empty = AllocRingBuffer::from(ConstGenericRingBuffer::<i32, 3>::new());
+    assert_eq!(empty.capacity(), 3);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_const_alloc_round_trip_drops() {
+    use std::rc::Rc;
+
+    let value = Rc::new(());
+    let mut a = ConstGenericRingBuffer::<_, 3>::new();
+    a.extend(core::iter::repeat(value.clone()).take(3));
+    assert_eq!(Rc::strong_count(&value), 4);
+
+    let b = AllocRingBuffer::from(a);
+    assert_eq!(Rc::strong_count(&value), 4);
+    let c = ConstGenericRingBuffer::<_, 3>::from(b);
+    assert_eq!(Rc::strong_count(&value), 4);
+
+    drop(c);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_slice_conversion_any_length() {
+    let power_of_two: &[i32] = &[1, 2, 3, 4];
+    let rb = AllocRingBuffer::from(power_of_two);
+    assert_eq!(rb.capacity(), 4);
+    assert_eq!(rb.buffer_size(), 4);
+    assert!(rb.capacity_is_power_of_two());
+    assert_eq!(rb.to_vec(), vec![1, 2, 3, 4]);
+
+    let other: &[i32] = &[1, 2, 3];
+    let rb = AllocRingBuffer::from(other);
+    assert_eq!(rb.capacity(), 3);
+    assert_eq!(rb.buffer_size(), 4);
+    assert!(!rb.capacity_is_power_of_two());
+    assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+}